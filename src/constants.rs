@@ -15,10 +15,19 @@ bitflags! {
     }
 }
 
-/// Calendar epoch identifiers. Only a subset is currently supported.
+/// Calendar epoch identifiers. Apart from `EPOCH_1904` these match the
+/// calendar byte of an Excel locale code such as `[$-70000]`.
 pub const EPOCH_1904: i32 = -1;
 pub const EPOCH_1900: i32 = 1;
+pub const EPOCH_JAPANESE: i32 = 3;
+pub const EPOCH_TAIWAN: i32 = 4;
+pub const EPOCH_KOREAN: i32 = 5;
 pub const EPOCH_1317: i32 = 6;
+pub const EPOCH_THAI: i32 = 7;
+pub const EPOCH_HEBREW: i32 = 8;
+
+/// Days between the 1900 and 1904 date system epochs.
+pub const EPOCH_1904_OFFSET: f64 = 1_462.0;
 
 /// Excel date boundaries.
 pub const MIN_S_DATE: f64 = 0.0;
@@ -0,0 +1,240 @@
+//! Caches of parsed patterns.
+//!
+//! A cache holds at most [`capacity`](CacheStats::capacity) patterns, split
+//! over independently locked shards, and evicts the least recently used
+//! pattern of a shard when it is full. A capacity of zero disables it.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::parser::model::Pattern;
+
+use super::NumfmtContext;
+
+const SHARDS: usize = 16;
+/// Capacity of [`PatternCache::default`] and the process-wide cache.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// Patterns currently cached.
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+#[derive(Clone)]
+pub(crate) enum CachedPattern {
+    Valid(Arc<Pattern>),
+    Invalid {
+        message: String,
+        fallback: Arc<Pattern>,
+    },
+}
+
+struct Entry {
+    value: CachedPattern,
+    last_used: u64,
+}
+
+/// Patterns of one shard, with their keys also ordered by last use so that
+/// the least recently used one is found without a scan.
+#[derive(Default)]
+struct Shard {
+    entries: HashMap<String, Entry>,
+    by_use: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl Shard {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn get(&mut self, pattern: &str) -> Option<CachedPattern> {
+        let tick = self.tick();
+        let entry = self.entries.get_mut(pattern)?;
+        let key = self.by_use.remove(&entry.last_used)?;
+        entry.last_used = tick;
+        self.by_use.insert(tick, key);
+        Some(entry.value.clone())
+    }
+
+    fn insert(&mut self, pattern: &str, value: CachedPattern) {
+        let last_used = self.tick();
+        let entry = Entry { value, last_used };
+        if let Some(old) = self.entries.insert(pattern.to_string(), entry) {
+            self.by_use.remove(&old.last_used);
+        }
+        self.by_use.insert(last_used, pattern.to_string());
+    }
+
+    /// Drops least recently used entries until at most `limit` remain.
+    fn shrink_to(&mut self, limit: usize) -> u64 {
+        let mut evicted = 0;
+        while self.entries.len() > limit {
+            let Some((_, key)) = self.by_use.pop_first() else {
+                break;
+            };
+            self.entries.remove(&key);
+            evicted += 1;
+        }
+        evicted
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.by_use.clear();
+    }
+}
+
+/// Size-bounded cache of parsed patterns. Every [`NumfmtContext`] owns one;
+/// the free functions of this module act on the process-wide context's.
+///
+/// [`NumfmtContext`]: super::NumfmtContext
+pub struct PatternCache {
+    shards: [Mutex<Shard>; SHARDS],
+    capacity: AtomicUsize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl PatternCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            shards: std::array::from_fn(|_| Mutex::new(Shard::default())),
+            capacity: AtomicUsize::new(capacity),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    fn shard(&self, index: usize) -> MutexGuard<'_, Shard> {
+        self.shards[index]
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Share of the total capacity held by one shard; the shares add up to
+    /// exactly `capacity`. Small capacities use fewer shards so that every
+    /// pattern can be cached.
+    fn shard_capacity(capacity: usize, index: usize) -> usize {
+        let active = capacity.clamp(1, SHARDS);
+        capacity / active + usize::from(index < capacity % active)
+    }
+
+    /// Locks the shard of `pattern` together with the capacity it was picked
+    /// for, so that a concurrent [`set_capacity`](Self::set_capacity) either
+    /// sees the shard's changes or is seen by them.
+    fn lock(&self, pattern: &str) -> (MutexGuard<'_, Shard>, usize) {
+        let mut hasher = DefaultHasher::new();
+        pattern.hash(&mut hasher);
+        let hash = hasher.finish() as usize;
+        loop {
+            let active = self.capacity.load(Ordering::Relaxed).clamp(1, SHARDS);
+            let index = hash % active;
+            let shard = self.shard(index);
+            let capacity = self.capacity.load(Ordering::Relaxed);
+            // retry if the cache was resharded before the lock was taken
+            if capacity.clamp(1, SHARDS) == active {
+                return (shard, Self::shard_capacity(capacity, index));
+            }
+        }
+    }
+
+    pub(crate) fn get(&self, pattern: &str) -> Option<CachedPattern> {
+        let found = self.lock(pattern).0.get(pattern);
+        let counter = if found.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    pub(crate) fn insert(&self, pattern: &str, value: CachedPattern) {
+        let (mut shard, limit) = self.lock(pattern);
+        if limit == 0 {
+            return;
+        }
+        if !shard.entries.contains_key(pattern) {
+            let evicted = shard.shrink_to(limit - 1);
+            self.evictions.fetch_add(evicted, Ordering::Relaxed);
+        }
+        shard.insert(pattern, value);
+    }
+
+    /// Sets the maximum number of cached patterns, evicting the least
+    /// recently used ones if the cache is over the new limit. Zero disables
+    /// caching.
+    pub fn set_capacity(&self, capacity: usize) {
+        let previous = self.capacity.swap(capacity, Ordering::Relaxed);
+        // patterns hash to other shards once the shard count changes
+        let reshard = previous.clamp(1, SHARDS) != capacity.clamp(1, SHARDS);
+        for index in 0..SHARDS {
+            let mut shard = self.shard(index);
+            let limit = if reshard {
+                0
+            } else {
+                Self::shard_capacity(self.capacity.load(Ordering::Relaxed), index)
+            };
+            let evicted = shard.shrink_to(limit);
+            self.evictions.fetch_add(evicted, Ordering::Relaxed);
+        }
+    }
+
+    /// Removes every cached pattern. Counters are kept.
+    pub fn clear(&self) {
+        for index in 0..SHARDS {
+            self.shard(index).clear();
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: (0..SHARDS)
+                .map(|index| self.shard(index).entries.len())
+                .sum(),
+            capacity: self.capacity.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for PatternCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl fmt::Debug for PatternCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PatternCache").field(&self.stats()).finish()
+    }
+}
+
+/// See [`PatternCache::set_capacity`].
+pub fn set_pattern_cache_capacity(capacity: usize) {
+    NumfmtContext::global().cache().set_capacity(capacity);
+}
+
+/// See [`PatternCache::clear`].
+pub fn clear_pattern_cache() {
+    NumfmtContext::global().cache().clear();
+}
+
+pub fn pattern_cache_stats() -> CacheStats {
+    NumfmtContext::global().cache().stats()
+}
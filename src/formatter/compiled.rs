@@ -0,0 +1,100 @@
+use std::fmt::Write;
+use std::sync::Arc;
+
+use crate::parser::model::Pattern;
+
+use super::{
+    CellLayout, ColorValue, FormatValue, FormattedPart, FormatterError, FormatterOptions,
+    NumfmtContext, locale::Locale, pattern_color, pattern_layout, pattern_parts, write_pattern,
+};
+
+/// Pattern parsed and locale resolved once, for formatting many values. It
+/// takes no global locks after construction and can be shared across threads.
+///
+/// The locale is captured at construction, so later [`add_locale`](super::add_locale)
+/// or [`remove_locale`](super::remove_locale) calls do not affect it.
+#[derive(Debug, Clone)]
+pub struct CompiledFormat {
+    pattern: Arc<Pattern>,
+    locale: Arc<Locale>,
+    options: FormatterOptions,
+}
+
+impl CompiledFormat {
+    /// Compiles against the [global](NumfmtContext::global) context. Fails on
+    /// an invalid pattern only when [`FormatterOptions::throws`] is set;
+    /// otherwise the handle renders the error like
+    /// [`format_with_options`](super::format_with_options).
+    pub fn new(pattern: &str, options: FormatterOptions) -> Result<Self, FormatterError> {
+        Self::build(NumfmtContext::global(), pattern, options)
+    }
+
+    pub(crate) fn build(
+        context: &NumfmtContext,
+        pattern: &str,
+        options: FormatterOptions,
+    ) -> Result<Self, FormatterError> {
+        let pattern = context.prepare(pattern, options.throws)?;
+        let locale = context.locale_for(&pattern, &options);
+        Ok(Self {
+            pattern,
+            locale,
+            options,
+        })
+    }
+
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    pub fn options(&self) -> &FormatterOptions {
+        &self.options
+    }
+
+    pub fn format<'a, V>(&self, value: V) -> Result<String, FormatterError>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        let mut out = String::new();
+        self.format_into(&mut out, value)?;
+        Ok(out)
+    }
+
+    /// Writes into `out`, which may hold partial output when an error is returned.
+    pub fn format_into<'a, W, V>(&self, out: &mut W, value: V) -> Result<(), FormatterError>
+    where
+        W: Write + ?Sized,
+        V: Into<FormatValue<'a>>,
+    {
+        write_pattern(
+            out,
+            &self.pattern,
+            &self.locale,
+            &value.into(),
+            &self.options,
+        )
+    }
+
+    /// See [`format_parts`](super::format_parts).
+    pub fn format_parts<'a, V>(&self, value: V) -> Result<Vec<FormattedPart>, FormatterError>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        pattern_parts(&self.pattern, &self.locale, &value.into(), &self.options)
+    }
+
+    /// See [`format_layout`](super::format_layout).
+    pub fn format_layout<'a, V>(&self, value: V) -> Result<CellLayout, FormatterError>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        pattern_layout(&self.pattern, &self.locale, &value.into(), &self.options)
+    }
+
+    pub fn color<'a, V>(&self, value: V) -> Option<ColorValue>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        pattern_color(&self.pattern, &value.into(), &self.options)
+    }
+}
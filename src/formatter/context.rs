@@ -0,0 +1,236 @@
+use std::fmt::Write;
+use std::sync::{Arc, OnceLock};
+
+use crate::parser::model::Pattern;
+
+use super::{
+    CellLayout, ColorValue, CompiledFormat, FormatValue, Formatted, FormattedPart, FormatterError,
+    FormatterOptions,
+    cache::PatternCache,
+    locale::{self, Locale, LocaleError, LocaleRegistry, LocaleSettings},
+    pattern_color, pattern_layout, pattern_parts, prepare_pattern, resolve_locale_tag,
+    write_pattern,
+};
+
+/// Locale registry, pattern cache and default options that formatting runs
+/// against. The free functions such as [`format`](super::format) and
+/// [`add_locale`](super::add_locale) use the [`global`](Self::global)
+/// context; separate contexts keep tenants or tests from seeing each other's
+/// locales and patterns.
+#[derive(Debug)]
+pub struct NumfmtContext {
+    locales: Arc<LocaleRegistry>,
+    cache: PatternCache,
+    options: FormatterOptions,
+}
+
+static GLOBAL: OnceLock<NumfmtContext> = OnceLock::new();
+
+impl NumfmtContext {
+    /// Context with the built-in locales, an empty cache and default options.
+    pub fn new() -> Self {
+        Self::with_registry(Arc::new(LocaleRegistry::new()))
+    }
+
+    pub fn global() -> &'static Self {
+        GLOBAL.get_or_init(|| Self::with_registry(locale::registry().clone()))
+    }
+
+    fn with_registry(locales: Arc<LocaleRegistry>) -> Self {
+        Self {
+            locales,
+            cache: PatternCache::default(),
+            options: FormatterOptions::default(),
+        }
+    }
+
+    /// Options used by the methods that take none.
+    pub fn with_options(mut self, options: FormatterOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_locales(mut self, locales: Arc<LocaleRegistry>) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    pub fn with_cache_capacity(self, capacity: usize) -> Self {
+        self.cache.set_capacity(capacity);
+        self
+    }
+
+    pub fn options(&self) -> &FormatterOptions {
+        &self.options
+    }
+
+    pub fn locales(&self) -> &Arc<LocaleRegistry> {
+        &self.locales
+    }
+
+    pub fn cache(&self) -> &PatternCache {
+        &self.cache
+    }
+
+    pub fn add_locale(
+        &self,
+        settings: LocaleSettings,
+        tag: impl AsRef<str>,
+    ) -> Result<(), LocaleError> {
+        self.locales.add(settings, tag.as_ref())
+    }
+
+    pub fn remove_locale(&self, tag: impl AsRef<str>) -> bool {
+        self.locales.remove(tag.as_ref())
+    }
+
+    pub fn format<'a, V>(&self, pattern: &str, value: V) -> Result<String, FormatterError>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        self.format_with_options(pattern, value, &self.options)
+    }
+
+    pub fn format_with_options<'a, V>(
+        &self,
+        pattern: &str,
+        value: V,
+        options: &FormatterOptions,
+    ) -> Result<String, FormatterError>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        let mut out = String::new();
+        self.write_value(&mut out, pattern, &value.into(), options)?;
+        Ok(out)
+    }
+
+    /// Writes into `out`, which may hold partial output when an error is returned.
+    pub fn format_into<'a, W, V>(
+        &self,
+        out: &mut W,
+        pattern: &str,
+        value: V,
+    ) -> Result<(), FormatterError>
+    where
+        W: Write + ?Sized,
+        V: Into<FormatValue<'a>>,
+    {
+        self.write_value(out, pattern, &value.into(), &self.options)
+    }
+
+    /// See [`format_parts`](super::format_parts).
+    pub fn format_parts<'a, V>(
+        &self,
+        pattern: &str,
+        value: V,
+    ) -> Result<Vec<FormattedPart>, FormatterError>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        self.parts(pattern, &value.into(), &self.options)
+    }
+
+    /// See [`format_layout`](super::format_layout).
+    pub fn format_layout<'a, V>(
+        &self,
+        pattern: &str,
+        value: V,
+    ) -> Result<CellLayout, FormatterError>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        self.layout(pattern, &value.into(), &self.options)
+    }
+
+    pub fn format_color<'a, V>(
+        &self,
+        pattern: &str,
+        value: V,
+    ) -> Result<Option<ColorValue>, FormatterError>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        self.color(pattern, &value.into(), &self.options)
+    }
+
+    pub fn compile(&self, pattern: &str) -> Result<CompiledFormat, FormatterError> {
+        CompiledFormat::build(self, pattern, self.options.clone())
+    }
+
+    pub fn display<'a>(
+        &'a self,
+        pattern: &'a str,
+        value: impl Into<FormatValue<'a>>,
+    ) -> Formatted<'a> {
+        Formatted::in_context(self, pattern, value.into(), &self.options)
+    }
+
+    pub(crate) fn prepare(
+        &self,
+        pattern: &str,
+        should_throw: bool,
+    ) -> Result<Arc<Pattern>, FormatterError> {
+        prepare_pattern(&self.cache, pattern, should_throw)
+    }
+
+    /// Locale of a pattern, looked up in the options' registry if they carry
+    /// one and in the context's otherwise.
+    pub(crate) fn locale_for(&self, pattern: &Pattern, options: &FormatterOptions) -> Arc<Locale> {
+        options
+            .locales
+            .as_ref()
+            .unwrap_or(&self.locales)
+            .get_or_default(resolve_locale_tag(pattern, options))
+    }
+
+    pub(crate) fn write_value<W: Write + ?Sized>(
+        &self,
+        out: &mut W,
+        pattern: &str,
+        value: &FormatValue<'_>,
+        options: &FormatterOptions,
+    ) -> Result<(), FormatterError> {
+        let parsed = self.prepare(pattern, options.throws)?;
+        let locale = self.locale_for(&parsed, options);
+        write_pattern(out, &parsed, &locale, value, options)
+    }
+
+    pub(crate) fn parts(
+        &self,
+        pattern: &str,
+        value: &FormatValue<'_>,
+        options: &FormatterOptions,
+    ) -> Result<Vec<FormattedPart>, FormatterError> {
+        let parsed = self.prepare(pattern, options.throws)?;
+        let locale = self.locale_for(&parsed, options);
+        pattern_parts(&parsed, &locale, value, options)
+    }
+
+    pub(crate) fn layout(
+        &self,
+        pattern: &str,
+        value: &FormatValue<'_>,
+        options: &FormatterOptions,
+    ) -> Result<CellLayout, FormatterError> {
+        let parsed = self.prepare(pattern, options.throws)?;
+        let locale = self.locale_for(&parsed, options);
+        pattern_layout(&parsed, &locale, value, options)
+    }
+
+    pub(crate) fn color(
+        &self,
+        pattern: &str,
+        value: &FormatValue<'_>,
+        options: &FormatterOptions,
+    ) -> Result<Option<ColorValue>, FormatterError> {
+        let parsed = self.prepare(pattern, options.throws)?;
+        Ok(pattern_color(&parsed, value, options))
+    }
+}
+
+impl Default for NumfmtContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
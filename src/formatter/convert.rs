@@ -0,0 +1,245 @@
+//! Conversions between [`DateValue`] and the `chrono`, `time` and `jiff`
+//! date types. Sub-millisecond precision is truncated, as `DateValue` only
+//! carries milliseconds; combine with [`DateValue::from_serial`] to read
+//! serials back into those types.
+
+use super::error::FormatterError;
+use super::value::{DateValue, FormatValue};
+
+macro_rules! format_value_from {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl<'a> From<$ty> for FormatValue<'a> {
+                fn from(value: $ty) -> Self {
+                    Self::Date(value.into())
+                }
+            }
+        )*
+    };
+}
+
+fn civil(year: i32, month: u8, day: u8) -> DateValue {
+    DateValue::new(year).with_month(month).with_day(day)
+}
+
+fn clock(date: DateValue, hour: u8, minute: u8, second: u8, nanos: u32) -> DateValue {
+    // Leap seconds report up to 1_999_999_999 nanoseconds.
+    let ms = (nanos / 1_000_000).min(999) as u16;
+    date.with_time(hour, minute, second).with_millisecond(ms)
+}
+
+#[cfg(any(feature = "time", feature = "jiff"))]
+fn out_of_bounds<E>(_: E) -> FormatterError {
+    FormatterError::DateOutOfBounds
+}
+
+impl DateValue {
+    fn date_parts(&self) -> (i32, u8, u8) {
+        (self.year, self.month.unwrap_or(1), self.day.unwrap_or(1))
+    }
+
+    fn time_parts(&self) -> (u8, u8, u8, u16) {
+        (
+            self.hour.unwrap_or(0),
+            self.minute.unwrap_or(0),
+            self.second.unwrap_or(0),
+            self.millisecond.unwrap_or(0),
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod with_chrono {
+    use chrono::{
+        DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike,
+    };
+
+    use super::*;
+
+    impl From<NaiveDate> for DateValue {
+        fn from(value: NaiveDate) -> Self {
+            civil(value.year(), value.month() as u8, value.day() as u8)
+        }
+    }
+
+    impl From<NaiveDateTime> for DateValue {
+        fn from(value: NaiveDateTime) -> Self {
+            clock(
+                value.date().into(),
+                value.hour() as u8,
+                value.minute() as u8,
+                value.second() as u8,
+                value.nanosecond(),
+            )
+        }
+    }
+
+    /// Keeps the value's offset, so formatting converts it into
+    /// [`FormatterOptions::timezone`](crate::FormatterOptions::timezone), UTC
+    /// by default, unless `ignore_timezone` is set.
+    impl<Tz: TimeZone> From<DateTime<Tz>> for DateValue {
+        fn from(value: DateTime<Tz>) -> Self {
+            let offset = value.offset().fix().local_minus_utc();
+            DateValue::from(value.naive_local()).with_offset(offset)
+        }
+    }
+
+    impl<'a, Tz: TimeZone> From<DateTime<Tz>> for FormatValue<'a> {
+        fn from(value: DateTime<Tz>) -> Self {
+            Self::Date(value.into())
+        }
+    }
+
+    format_value_from!(NaiveDate, NaiveDateTime);
+
+    impl TryFrom<DateValue> for NaiveDate {
+        type Error = FormatterError;
+
+        fn try_from(value: DateValue) -> Result<Self, Self::Error> {
+            let (year, month, day) = value.date_parts();
+            NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+                .ok_or(FormatterError::DateOutOfBounds)
+        }
+    }
+
+    impl TryFrom<DateValue> for NaiveDateTime {
+        type Error = FormatterError;
+
+        fn try_from(value: DateValue) -> Result<Self, Self::Error> {
+            let (hour, minute, second, ms) = value.time_parts();
+            let time =
+                NaiveTime::from_hms_milli_opt(hour as u32, minute as u32, second as u32, ms as u32)
+                    .ok_or(FormatterError::DateOutOfBounds)?;
+            Ok(NaiveDate::try_from(value)?.and_time(time))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod with_time {
+    use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+    use super::*;
+
+    impl From<Date> for DateValue {
+        fn from(value: Date) -> Self {
+            civil(value.year(), value.month() as u8, value.day())
+        }
+    }
+
+    impl From<PrimitiveDateTime> for DateValue {
+        fn from(value: PrimitiveDateTime) -> Self {
+            clock(
+                value.date().into(),
+                value.hour(),
+                value.minute(),
+                value.second(),
+                value.nanosecond(),
+            )
+        }
+    }
+
+    /// Keeps the value's offset, so formatting converts it into
+    /// [`FormatterOptions::timezone`](crate::FormatterOptions::timezone), UTC
+    /// by default, unless `ignore_timezone` is set.
+    impl From<OffsetDateTime> for DateValue {
+        fn from(value: OffsetDateTime) -> Self {
+            let wall = PrimitiveDateTime::new(value.date(), value.time());
+            DateValue::from(wall).with_offset(value.offset().whole_seconds())
+        }
+    }
+
+    format_value_from!(Date, PrimitiveDateTime, OffsetDateTime);
+
+    impl TryFrom<DateValue> for Date {
+        type Error = FormatterError;
+
+        fn try_from(value: DateValue) -> Result<Self, Self::Error> {
+            let (year, month, day) = value.date_parts();
+            let month = Month::try_from(month).map_err(out_of_bounds)?;
+            Date::from_calendar_date(year, month, day).map_err(out_of_bounds)
+        }
+    }
+
+    impl TryFrom<DateValue> for PrimitiveDateTime {
+        type Error = FormatterError;
+
+        fn try_from(value: DateValue) -> Result<Self, Self::Error> {
+            let (hour, minute, second, ms) = value.time_parts();
+            let time = Time::from_hms_milli(hour, minute, second, ms).map_err(out_of_bounds)?;
+            Ok(PrimitiveDateTime::new(Date::try_from(value)?, time))
+        }
+    }
+}
+
+#[cfg(feature = "jiff")]
+mod with_jiff {
+    use jiff::{
+        Timestamp, Zoned,
+        civil::{Date, DateTime},
+    };
+
+    use super::*;
+
+    impl From<Date> for DateValue {
+        fn from(value: Date) -> Self {
+            civil(value.year() as i32, value.month() as u8, value.day() as u8)
+        }
+    }
+
+    impl From<DateTime> for DateValue {
+        fn from(value: DateTime) -> Self {
+            clock(
+                value.date().into(),
+                value.hour() as u8,
+                value.minute() as u8,
+                value.second() as u8,
+                value.subsec_nanosecond() as u32,
+            )
+        }
+    }
+
+    /// Keeps the value's offset, so formatting converts it into
+    /// [`FormatterOptions::timezone`](crate::FormatterOptions::timezone), UTC
+    /// by default, unless `ignore_timezone` is set.
+    impl From<Zoned> for DateValue {
+        fn from(value: Zoned) -> Self {
+            DateValue::from(value.datetime()).with_offset(value.offset().seconds())
+        }
+    }
+
+    impl From<Timestamp> for DateValue {
+        fn from(value: Timestamp) -> Self {
+            DateValue::from(jiff::tz::TimeZone::UTC.to_datetime(value)).with_offset(0)
+        }
+    }
+
+    format_value_from!(Date, DateTime, Zoned, Timestamp);
+
+    impl TryFrom<DateValue> for Date {
+        type Error = FormatterError;
+
+        fn try_from(value: DateValue) -> Result<Self, Self::Error> {
+            let (year, month, day) = value.date_parts();
+            let year = i16::try_from(year).map_err(out_of_bounds)?;
+            Date::new(year, month as i8, day as i8).map_err(out_of_bounds)
+        }
+    }
+
+    impl TryFrom<DateValue> for DateTime {
+        type Error = FormatterError;
+
+        fn try_from(value: DateValue) -> Result<Self, Self::Error> {
+            let (hour, minute, second, ms) = value.time_parts();
+            let date = Date::try_from(value)?;
+            let time = jiff::civil::Time::new(
+                hour as i8,
+                minute as i8,
+                second as i8,
+                ms as i32 * 1_000_000,
+            )
+            .map_err(out_of_bounds)?;
+            Ok(date.to_datetime(time))
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::{self, Write};
+
+const INLINE: usize = 48;
+
+/// Short string kept on the stack, used for the digit runs of a number. It
+/// only moves to the heap for values longer than `INLINE` bytes.
+pub(crate) struct Digits {
+    inline: [u8; INLINE],
+    len: usize,
+    spill: Option<String>,
+}
+
+impl Digits {
+    pub fn new() -> Self {
+        Self {
+            inline: [0; INLINE],
+            len: 0,
+            spill: None,
+        }
+    }
+
+    pub fn display(value: impl fmt::Display) -> Self {
+        let mut out = Self::new();
+        // writing into `Digits` cannot fail
+        let _ = write!(out, "{value}");
+        out
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.spill {
+            Some(text) => text,
+            None => std::str::from_utf8(&self.inline[..self.len]).expect("whole chars"),
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.spill = None;
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        if let Some(spill) = &mut self.spill {
+            spill.push_str(text);
+        } else if self.len + text.len() <= INLINE {
+            self.inline[self.len..self.len + text.len()].copy_from_slice(text.as_bytes());
+            self.len += text.len();
+        } else {
+            let mut spill = String::with_capacity(self.len + text.len());
+            spill.push_str(self.as_str());
+            spill.push_str(text);
+            self.spill = Some(spill);
+        }
+    }
+
+    /// Shortens to `len` bytes, which must fall on a char boundary.
+    pub fn truncate(&mut self, len: usize) {
+        match &mut self.spill {
+            Some(spill) => spill.truncate(len),
+            None => self.len = self.len.min(len),
+        }
+    }
+}
+
+impl fmt::Write for Digits {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.push_str(text);
+        Ok(())
+    }
+}
@@ -6,9 +6,12 @@ use crate::parser::error::ParseError;
 pub enum FormatterError {
     Parse(ParseError),
     DateOutOfBounds,
+    InvalidDate(f64),
     InvalidPattern(String),
     InvalidLocale(String),
+    InvalidTimeZone(String),
     BigIntOverflow,
+    Write(fmt::Error),
     Other(String),
 }
 
@@ -17,9 +20,14 @@ impl fmt::Display for FormatterError {
         match self {
             FormatterError::Parse(err) => write!(f, "{}", err),
             FormatterError::DateOutOfBounds => write!(f, "Date out of bounds"),
+            FormatterError::InvalidDate(serial) => {
+                write!(f, "Serial {serial} has no date in this calendar")
+            }
             FormatterError::InvalidPattern(pat) => write!(f, "Invalid pattern: {pat}"),
             FormatterError::InvalidLocale(tag) => write!(f, "Invalid locale: {tag}"),
+            FormatterError::InvalidTimeZone(name) => write!(f, "Invalid time zone: {name}"),
             FormatterError::BigIntOverflow => write!(f, "BigInt value out of range"),
+            FormatterError::Write(err) => write!(f, "Failed to write output: {err}"),
             FormatterError::Other(msg) => write!(f, "{msg}"),
         }
     }
@@ -32,3 +40,9 @@ impl From<ParseError> for FormatterError {
         Self::Parse(value)
     }
 }
+
+impl From<fmt::Error> for FormatterError {
+    fn from(value: fmt::Error) -> Self {
+        Self::Write(value)
+    }
+}
@@ -0,0 +1,211 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use num_bigint::BigInt;
+use num_traits::Signed;
+
+use super::options::RoundingMode;
+
+/// Exact decimal `digits × 10^exp` for values that must not pass through
+/// `f64`. `digits` carries no leading or trailing zeros and is empty for zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Exact {
+    negative: bool,
+    digits: String,
+    exp: i32,
+}
+
+impl Exact {
+    pub fn new(negative: bool, digits: &str, exp: i32) -> Self {
+        let digits = digits.trim_start_matches('0');
+        let trimmed = digits.trim_end_matches('0');
+        if trimmed.is_empty() {
+            return Self {
+                negative: false,
+                digits: String::new(),
+                exp: 0,
+            };
+        }
+        Self {
+            negative,
+            digits: trimmed.to_string(),
+            exp: exp + (digits.len() - trimmed.len()) as i32,
+        }
+    }
+
+    /// Digits of the shortest representation that round-trips `value`.
+    pub fn from_f64(value: f64) -> Self {
+        let repr = value.abs().to_string();
+        let (int, frac) = repr.split_once('.').unwrap_or((&repr, ""));
+        Self::new(
+            value.is_sign_negative(),
+            &format!("{int}{frac}"),
+            -(frac.len() as i32),
+        )
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Multiplies by `10^places`.
+    pub fn shift(&self, places: i32) -> Self {
+        let mut shifted = self.clone();
+        if !shifted.digits.is_empty() {
+            shifted.exp += places;
+        }
+        shifted
+    }
+
+    /// Rounds to `places` fraction digits.
+    pub fn round(&self, places: usize, mode: RoundingMode) -> Self {
+        let drop = -(self.exp + places as i32);
+        if drop <= 0 {
+            return self.clone();
+        }
+        let drop = drop as usize;
+        let len = self.digits.len();
+        // The dropped digits are never all zero, as trailing zeros are trimmed
+        let (keep, rest) = self.digits.split_at(len.saturating_sub(drop));
+        let half = if drop > len {
+            Ordering::Less
+        } else {
+            rest.as_bytes()[0].cmp(&b'5').then(if rest.len() > 1 {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            })
+        };
+        let up = match mode {
+            RoundingMode::HalfAwayFromZero => half != Ordering::Less,
+            RoundingMode::HalfEven => {
+                let odd = keep.bytes().last().is_some_and(|b| (b - b'0') % 2 == 1);
+                half == Ordering::Greater || (half == Ordering::Equal && odd)
+            }
+            RoundingMode::Truncate => false,
+            RoundingMode::Floor => self.negative,
+            RoundingMode::Ceiling => !self.negative,
+        };
+        let mut kept = keep.to_string();
+        if up {
+            increment(&mut kept);
+        }
+        Self::new(self.negative, &kept, self.exp + drop as i32)
+    }
+
+    /// Significand and exponent for scientific patterns, following
+    /// `math::get_exponent` and the mantissa carry of `run_part`.
+    pub fn scientific(
+        &self,
+        int_max: usize,
+        integer: bool,
+        frac_max: usize,
+        mode: RoundingMode,
+    ) -> (Self, i32) {
+        if self.is_zero() {
+            return (self.clone(), 0);
+        }
+        let magnitude = self.digits.len() as i32 + self.exp - 1;
+        let mut exponent = if int_max > 1 {
+            magnitude.div_euclid(int_max as i32) * int_max as i32
+        } else {
+            magnitude
+        };
+        if !integer {
+            exponent += 1;
+        }
+        let mut significand = self.shift(-exponent);
+        let rounded = significand.round(frac_max, mode);
+        if int_max == 1 && rounded.integer() == "10" && rounded.fraction().is_empty() {
+            significand = Self::new(self.negative, "1", 0);
+            exponent += 1;
+        }
+        (significand, exponent)
+    }
+
+    /// Digits of the integer part, empty when the magnitude is below one.
+    pub fn integer(&self) -> String {
+        let len = self.digits.len() as i32;
+        if self.exp >= 0 {
+            let mut out = self.digits.clone();
+            out.extend(std::iter::repeat_n('0', self.exp as usize));
+            out
+        } else if len + self.exp > 0 {
+            self.digits[..(len + self.exp) as usize].to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    /// Digits after the decimal point, without trailing zeros.
+    pub fn fraction(&self) -> String {
+        if self.exp >= 0 {
+            return String::new();
+        }
+        let places = (-self.exp) as usize;
+        let len = self.digits.len();
+        if places >= len {
+            let mut out: String = std::iter::repeat_n('0', places - len).collect();
+            out.push_str(&self.digits);
+            out
+        } else {
+            self.digits[len - places..].to_string()
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(0.0)
+    }
+}
+
+impl fmt::Display for Exact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            f.write_str("-")?;
+        }
+        let integer = self.integer();
+        f.write_str(if integer.is_empty() { "0" } else { &integer })?;
+        let fraction = self.fraction();
+        if !fraction.is_empty() {
+            write!(f, ".{fraction}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&BigInt> for Exact {
+    fn from(value: &BigInt) -> Self {
+        Self::new(value.is_negative(), &value.magnitude().to_string(), 0)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for Exact {
+    fn from(value: rust_decimal::Decimal) -> Self {
+        let mantissa = value.mantissa();
+        Self::new(
+            mantissa < 0,
+            &mantissa.unsigned_abs().to_string(),
+            -(value.scale() as i32),
+        )
+    }
+}
+
+fn increment(digits: &mut String) {
+    let mut bytes = std::mem::take(digits).into_bytes();
+    let mut idx = bytes.len();
+    loop {
+        if idx == 0 {
+            bytes.insert(0, b'1');
+            break;
+        }
+        idx -= 1;
+        if bytes[idx] == b'9' {
+            bytes[idx] = b'0';
+        } else {
+            bytes[idx] += 1;
+            break;
+        }
+    }
+    *digits = String::from_utf8(bytes).expect("ascii digits");
+}
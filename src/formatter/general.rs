@@ -1,37 +1,80 @@
-use crate::parser::model::Section;
+use std::fmt::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
 
 use super::{
-    locale::Locale,
+    digits::Digits,
+    exact::Exact,
+    locale::{self, Locale},
     math::{get_exponent, get_significand, numdec, round},
+    options::RoundingMode,
 };
 
-fn fix_locale(input: &str, locale: &Locale) -> String {
+/// Writes `input` with its decimal point replaced by the locale's.
+fn write_localized<W: Write + ?Sized>(out: &mut W, input: &str, locale: &Locale) -> fmt::Result {
     if locale.decimal == "." {
-        input.to_string()
-    } else {
-        input.replace('.', &locale.decimal)
+        return out.write_str(input);
+    }
+    let mut pieces = input.split('.');
+    if let Some(first) = pieces.next() {
+        out.write_str(first)?;
     }
+    for piece in pieces {
+        out.write_str(&locale.decimal)?;
+        out.write_str(piece)?;
+    }
+    Ok(())
 }
 
-fn exponent_string(n: f64, exp: i32, locale: &Locale) -> String {
-    let abs_exp = exp.abs();
-    let mut out = String::new();
-    let mantissa = round(n, 5);
-    out.push_str(&fix_locale(&mantissa.to_string(), locale));
-    out.push_str(&locale.exponent);
-    out.push_str(if exp < 0 {
+fn write_exponent<W: Write + ?Sized>(
+    out: &mut W,
+    n: f64,
+    exp: i32,
+    locale: &Locale,
+) -> fmt::Result {
+    let mut mantissa = Digits::new();
+    write!(mantissa, "{}", round(n, 5))?;
+    out_exponent(out, mantissa.as_str(), exp, locale)
+}
+
+/// General format of an exact integer: up to 11 digits in full, otherwise
+/// scientific with five decimals.
+pub fn format_general_exact<W: Write + ?Sized>(
+    out: &mut W,
+    value: &Exact,
+    locale: &Locale,
+) -> fmt::Result {
+    let digits = value.integer();
+    if digits.len() <= 11 {
+        return out.write_str(if digits.is_empty() { "0" } else { &digits });
+    }
+    let mut exp = digits.len() as i32 - 1;
+    let mut mantissa = value.shift(-exp).round(5, RoundingMode::HalfAwayFromZero);
+    if mantissa.integer() == "10" {
+        mantissa = mantissa.shift(-1);
+        exp += 1;
+    }
+    let mantissa = mantissa.to_string();
+    out_exponent(out, mantissa.trim_start_matches('-'), exp, locale)
+}
+
+fn out_exponent<W: Write + ?Sized>(
+    out: &mut W,
+    mantissa: &str,
+    exp: i32,
+    locale: &Locale,
+) -> fmt::Result {
+    write_localized(out, mantissa, locale)?;
+    out.write_str(&locale.exponent)?;
+    out.write_str(if exp < 0 {
         &locale.negative
     } else {
         &locale.positive
-    });
-    if abs_exp < 10 {
-        out.push('0');
-    }
-    out.push_str(&abs_exp.to_string());
-    out
+    })?;
+    write!(out, "{:02}", exp.abs())
 }
 
-pub fn format_general(buffer: &mut String, value: f64, _part: &Section, locale: &Locale) {
+pub fn format_general<W: Write + ?Sized>(out: &mut W, value: f64, locale: &Locale) -> fmt::Result {
     let int = value.trunc() as i64;
 
     if value == 0.0 || ((value - int as f64).abs() < f64::EPSILON && value.abs() >= 1.0) {
@@ -39,10 +82,10 @@ pub fn format_general(buffer: &mut String, value: f64, _part: &Section, locale:
         if abs_int < 0 {
             abs_int = -abs_int;
         }
-        let digits = abs_int.to_string();
+        let mut digits = Digits::new();
+        write!(digits, "{abs_int}")?;
         if digits.len() <= 11 {
-            buffer.push_str(&digits);
-            return;
+            return out.write_str(digits.as_str());
         }
     }
 
@@ -55,41 +98,118 @@ pub fn format_general(buffer: &mut String, value: f64, _part: &Section, locale:
     }
 
     let num_dig = numdec(v, true);
+    let mut o = Digits::new();
 
     if (-4..=-1).contains(&exp) {
-        let mut o = format!("{:.9}", v);
-        if o.contains('.') {
-            while o.ends_with('0') {
-                o.pop();
-            }
-            if o.ends_with('.') {
-                o.pop();
-            }
+        write!(o, "{:.9}", v)?;
+        if o.as_str().contains('.') {
+            let trimmed = o.as_str().trim_end_matches('0').trim_end_matches('.').len();
+            o.truncate(trimmed);
         }
-        buffer.push_str(&fix_locale(&o, locale));
+        write_localized(out, o.as_str(), locale)
     } else if exp == 10 {
-        let mut o = format!("{:.10}", v);
-        if o.len() > 12 {
-            o.truncate(12);
-        }
-        if o.ends_with('.') {
-            o.pop();
+        write!(o, "{:.10}", v)?;
+        o.truncate(12);
+        if o.as_str().ends_with('.') {
+            o.truncate(o.len() - 1);
         }
-        buffer.push_str(&fix_locale(&o, locale));
+        write_localized(out, o.as_str(), locale)
     } else if exp.abs() <= 9 {
         if num_dig.total <= 11 {
-            let o = round(v, 9);
-            let formatted = format!("{o:.prec$}", prec = num_dig.frac);
-            buffer.push_str(&fix_locale(&formatted, locale));
+            let rounded = round(v, 9);
+            write!(o, "{rounded:.prec$}", prec = num_dig.frac)?;
+            write_localized(out, o.as_str(), locale)
         } else if exp == 9 {
-            buffer.push_str(&v.floor().to_string());
+            write!(out, "{}", v.floor())
         } else if (0..9).contains(&exp) {
-            let o = round(v, (9 - exp) as usize);
-            buffer.push_str(&fix_locale(&o.to_string(), locale));
+            write!(o, "{}", round(v, (9 - exp) as usize))?;
+            write_localized(out, o.as_str(), locale)
         } else {
-            buffer.push_str(&exponent_string(n, exp, locale));
+            write_exponent(out, n, exp, locale)
         }
     } else {
-        buffer.push_str(&exponent_string(n, exp, locale));
+        write_exponent(out, n, exp, locale)
+    }
+}
+
+/// General format of `value` in at most `max_chars` terminal cells, sign
+/// included, for a locale tag looked up in the process-wide registry. Returns
+/// `None` when not even the shortest scientific form fits.
+///
+/// Values that fit keep Excel's usual 11-character General form; narrower
+/// budgets round away decimals, then switch to scientific notation.
+pub fn format_general_with_width(value: f64, max_chars: usize, locale: &str) -> Option<String> {
+    if !value.is_finite() {
+        return None;
+    }
+    let locale = locale::registry().get_or_default(Some(locale));
+    let mut out = String::new();
+    if value < 0.0 {
+        out.push_str(&locale.negative);
+    }
+    let max = max_chars.checked_sub(out.width())?;
+    format_general_fit(&mut out, value, max, &locale)
+        .ok()?
+        .then_some(out)
+}
+
+/// General format of `value` in at most `max` cells, the way Excel narrows a
+/// column: decimals are dropped first, then the value switches to scientific
+/// notation with fewer mantissa digits. Writes nothing and returns `false`
+/// when not even the shortest exponent form fits.
+pub fn format_general_fit<W: Write + ?Sized>(
+    out: &mut W,
+    value: f64,
+    max: usize,
+    locale: &Locale,
+) -> Result<bool, fmt::Error> {
+    let mut text = Digits::new();
+    format_general(&mut text, value, locale)?;
+    if text.as_str().width() <= max {
+        out.write_str(text.as_str())?;
+        return Ok(true);
+    }
+
+    let v = value.abs();
+    let exp = get_exponent(v, 0);
+    if v == 0.0 || exp >= -4 {
+        let int_digits = if v < 1.0 { 1 } else { exp as usize + 1 };
+        let decimals = max.saturating_sub(int_digits + 1).min(9);
+        for places in (0..=decimals).rev() {
+            let rounded = round(v, places);
+            if rounded == 0.0 && v != 0.0 {
+                break;
+            }
+            let mut fixed = Digits::new();
+            write!(fixed, "{rounded:.places$}")?;
+            if fixed.as_str().contains('.') {
+                let trimmed = fixed.as_str().trim_end_matches('0').trim_end_matches('.');
+                fixed.truncate(trimmed.len());
+            }
+            text.clear();
+            write_localized(&mut text, fixed.as_str(), locale)?;
+            if text.as_str().width() <= max {
+                out.write_str(text.as_str())?;
+                return Ok(true);
+            }
+        }
+    }
+
+    let significand = get_significand(v, exp);
+    for places in (0..=5).rev() {
+        let (mut mantissa, mut exp) = (round(significand, places), exp);
+        if mantissa >= 10.0 {
+            mantissa /= 10.0;
+            exp += 1;
+        }
+        let mut digits = Digits::new();
+        write!(digits, "{mantissa}")?;
+        text.clear();
+        out_exponent(&mut text, digits.as_str(), exp, locale)?;
+        if text.as_str().width() <= max {
+            out.write_str(text.as_str())?;
+            return Ok(true);
+        }
     }
+    Ok(false)
 }
@@ -1,15 +1,26 @@
 use std::collections::HashMap;
-use std::sync::{Mutex, OnceLock};
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use serde::Deserialize;
 use thiserror::Error;
 
+use super::options::FormatterOptions;
+
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum LocaleError {
     #[error("invalid locale tag: {0}")]
     InvalidTag(String),
 }
 
+/// An era of a locale's calendar, beginning on a proleptic Gregorian date.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Era {
+    pub start: [i32; 3],
+    /// Names rendered for `g`, `gg` and `ggg`.
+    pub names: Vec<String>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct LocaleSettings {
     pub group: Option<String>,
@@ -23,12 +34,16 @@ pub struct LocaleSettings {
     pub ampm: Option<Vec<String>>,
     pub mmmm6: Option<Vec<String>>,
     pub mmm6: Option<Vec<String>>,
+    pub mmmm8: Option<Vec<String>>,
+    pub mmm8: Option<Vec<String>>,
     pub mmmm: Option<Vec<String>>,
     pub mmm: Option<Vec<String>>,
     pub dddd: Option<Vec<String>>,
     pub ddd: Option<Vec<String>>,
     pub bool_values: Option<Vec<String>>,
     pub prefer_mdy: Option<bool>,
+    pub native_digits: Option<[char; 10]>,
+    pub eras: Option<Vec<Era>>,
 }
 
 impl LocaleSettings {
@@ -45,12 +60,16 @@ impl LocaleSettings {
             ampm: self.ampm.unwrap_or_else(|| base.ampm.clone()),
             mmmm6: self.mmmm6.unwrap_or_else(|| base.mmmm6.clone()),
             mmm6: self.mmm6.unwrap_or_else(|| base.mmm6.clone()),
+            mmmm8: self.mmmm8.unwrap_or_else(|| base.mmmm8.clone()),
+            mmm8: self.mmm8.unwrap_or_else(|| base.mmm8.clone()),
             mmmm: self.mmmm.unwrap_or_else(|| base.mmmm.clone()),
             mmm: self.mmm.unwrap_or_else(|| base.mmm.clone()),
             dddd: self.dddd.unwrap_or_else(|| base.dddd.clone()),
             ddd: self.ddd.unwrap_or_else(|| base.ddd.clone()),
             bool_values: self.bool_values.unwrap_or_else(|| base.bool_values.clone()),
             prefer_mdy: self.prefer_mdy.unwrap_or(base.prefer_mdy),
+            native_digits: self.native_digits.or(base.native_digits),
+            eras: self.eras.unwrap_or_else(|| base.eras.clone()),
         }
     }
 }
@@ -68,12 +87,19 @@ pub struct Locale {
     pub ampm: Vec<String>,
     pub mmmm6: Vec<String>,
     pub mmm6: Vec<String>,
+    /// Hebrew month names: Tishrei … Shevat, Adar, Adar I, Adar II, Nisan … Elul.
+    pub mmmm8: Vec<String>,
+    pub mmm8: Vec<String>,
     pub mmmm: Vec<String>,
     pub mmm: Vec<String>,
     pub dddd: Vec<String>,
     pub ddd: Vec<String>,
     pub bool_values: Vec<String>,
     pub prefer_mdy: bool,
+    /// Digits `0`-`9` of the locale's own script, if it has one.
+    pub native_digits: Option<[char; 10]>,
+    /// Eras in chronological order; empty when `g`/`e` use the Gregorian year.
+    pub eras: Vec<Era>,
 }
 
 impl Locale {
@@ -90,6 +116,16 @@ impl Locale {
             .map(|s| s.as_str())
             .unwrap_or("FALSE")
     }
+
+    /// The era containing a date together with the year counted within it.
+    pub fn era(&self, year: i32, month: u8, day: u8) -> Option<(&Era, i32)> {
+        let date = [year, month as i32, day as i32];
+        self.eras
+            .iter()
+            .rev()
+            .find(|era| era.start <= date)
+            .map(|era| (era, year - era.start[0] + 1))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -123,6 +159,10 @@ struct LocaleRaw {
     #[serde(default)]
     mmm6: Vec<String>,
     #[serde(default)]
+    mmmm8: Vec<String>,
+    #[serde(default)]
+    mmm8: Vec<String>,
+    #[serde(default)]
     mmmm: Vec<String>,
     #[serde(default)]
     mmm: Vec<String>,
@@ -134,6 +174,10 @@ struct LocaleRaw {
     bool_values: Vec<String>,
     #[serde(default, rename = "preferMDY")]
     prefer_mdy: bool,
+    #[serde(default)]
+    digits: String,
+    #[serde(default)]
+    eras: Vec<Era>,
 }
 
 #[derive(Debug, Clone)]
@@ -142,100 +186,197 @@ struct LocaleId {
     language: String,
 }
 
-struct LocaleRegistry {
-    default: &'static Locale,
-    locales: HashMap<String, &'static Locale>,
+/// Locales addressed by tag. Besides the process-wide registry behind
+/// [`add_locale`], separate registries can be handed to
+/// [`FormatterOptions::locales`](super::FormatterOptions::locales) so that
+/// tenants or tests do not see each other's locales.
+///
+/// Registries compare equal only to themselves.
+pub struct LocaleRegistry {
+    default: Arc<Locale>,
+    locales: RwLock<HashMap<String, Arc<Locale>>>,
+}
+
+struct Builtin {
+    default: Arc<Locale>,
+    locales: HashMap<String, Arc<Locale>>,
 }
 
-static REGISTRY: OnceLock<Mutex<LocaleRegistry>> = OnceLock::new();
+static BUILTIN: OnceLock<Builtin> = OnceLock::new();
+static REGISTRY: OnceLock<Arc<LocaleRegistry>> = OnceLock::new();
 static CODE_MAP: OnceLock<HashMap<u32, String>> = OnceLock::new();
 
-pub fn default_locale() -> &'static Locale {
-    registry().lock().expect("locale registry poisoned").default
+fn builtin() -> &'static Builtin {
+    BUILTIN.get_or_init(|| {
+        let raw: LocaleFile =
+            serde_json::from_str(include_str!("./locales.json")).expect("invalid locale data");
+        let locales = raw
+            .locales
+            .into_iter()
+            .map(|(key, value)| (canonicalize_key(&key), Arc::new(Locale::from_raw(value))))
+            .collect();
+        Builtin {
+            default: Arc::new(Locale::from_raw(raw.default)),
+            locales,
+        }
+    })
 }
 
-fn registry() -> &'static Mutex<LocaleRegistry> {
-    REGISTRY.get_or_init(|| Mutex::new(LocaleRegistry::load()))
+/// Registry of the process-wide [`NumfmtContext`](super::NumfmtContext).
+pub(crate) fn registry() -> &'static Arc<LocaleRegistry> {
+    REGISTRY.get_or_init(|| Arc::new(LocaleRegistry::new()))
 }
 
-pub fn get_locale(tag: Option<&str>) -> Option<&'static Locale> {
-    tag.and_then(lookup_locale)
+pub fn default_locale() -> &'static Locale {
+    &builtin().default
 }
 
-pub fn get_locale_or_default(tag: Option<&str>) -> &'static Locale {
-    get_locale(tag).unwrap_or_else(|| default_locale())
+/// Built-in locale for `tag`, unaffected by registered overrides.
+pub(crate) fn builtin_locale(tag: &str) -> Option<&'static Locale> {
+    builtin().locales.get(&canonicalize_key(tag)).map(|l| &**l)
 }
 
+/// Registers or replaces a locale in the process-wide registry.
 pub fn add_locale(settings: LocaleSettings, tag: impl AsRef<str>) -> Result<(), LocaleError> {
-    let mut registry = registry().lock().expect("locale registry poisoned");
-    registry.add_locale(settings, tag.as_ref())
+    registry().add(settings, tag.as_ref())
 }
 
-#[allow(dead_code)]
-pub fn resolve_locale(tag: &str) -> Option<String> {
-    resolve_code(tag).or_else(|| parse_locale_tag(tag).map(|id| id.lang))
+/// Removes a locale from the process-wide registry, returning whether it
+/// was registered.
+pub fn remove_locale(tag: impl AsRef<str>) -> bool {
+    registry().remove(tag.as_ref())
 }
 
-fn lookup_locale(tag: &str) -> Option<&'static Locale> {
-    if tag.trim().is_empty() {
-        return None;
-    }
-    let registry = registry().lock().expect("locale registry poisoned");
-    registry.lookup(tag)
+/// Tags known to the process-wide registry, sorted.
+pub fn locale_tags() -> Vec<String> {
+    registry().tags()
+}
+
+/// Locale for `tag` from the options' registry, or the process-wide one.
+pub(crate) fn options_locale(tag: Option<&str>, options: &FormatterOptions) -> Arc<Locale> {
+    options
+        .locales
+        .as_deref()
+        .unwrap_or(registry())
+        .get_or_default(tag)
+}
+
+pub fn resolve_locale(tag: &str) -> Option<String> {
+    resolve_code(tag).or_else(|| parse_locale_tag(tag).map(|id| id.lang))
 }
 
 impl LocaleRegistry {
-    fn load() -> Self {
-        let raw: LocaleFile =
-            serde_json::from_str(include_str!("./locales.json")).expect("invalid locale data");
+    /// Registry holding the built-in locales.
+    pub fn new() -> Self {
+        let builtin = builtin();
+        Self {
+            default: builtin.default.clone(),
+            locales: RwLock::new(builtin.locales.clone()),
+        }
+    }
 
-        let default = leak_locale(Locale::from_raw(raw.default));
-        let mut locales = HashMap::new();
+    /// Adds a locale built on the default one, replacing any locale already
+    /// registered under `tag`. A language-only alias (`de` for `de_AT`) is
+    /// added when there is none yet.
+    pub fn add(&self, settings: LocaleSettings, tag: &str) -> Result<(), LocaleError> {
+        let parsed =
+            parse_locale_tag(tag).ok_or_else(|| LocaleError::InvalidTag(tag.to_string()))?;
+        let locale = Arc::new(settings.apply(&self.default));
+        let mut locales = self.write();
+        if parsed.language != parsed.lang && !locales.contains_key(&parsed.language) {
+            locales.insert(parsed.language, locale.clone());
+        }
+        locales.insert(parsed.lang, locale);
+        Ok(())
+    }
 
-        for (key, value) in raw.locales {
-            let canonical = canonicalize_key(&key);
-            locales.insert(canonical, leak_locale(Locale::from_raw(value)));
+    /// Removes the locale registered under `tag`, together with its language
+    /// alias when that points to the same locale.
+    pub fn remove(&self, tag: &str) -> bool {
+        let Some(parsed) = parse_locale_tag(tag) else {
+            return false;
+        };
+        let mut locales = self.write();
+        let Some(removed) = locales.remove(&parsed.lang) else {
+            return false;
+        };
+        if locales
+            .get(&parsed.language)
+            .is_some_and(|alias| Arc::ptr_eq(alias, &removed))
+        {
+            locales.remove(&parsed.language);
         }
+        true
+    }
 
-        Self { default, locales }
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.read().keys().cloned().collect();
+        tags.sort();
+        tags
     }
 
-    fn lookup(&self, tag: &str) -> Option<&'static Locale> {
+    pub fn contains(&self, tag: &str) -> bool {
+        self.get(tag).is_some()
+    }
+
+    pub(crate) fn get_or_default(&self, tag: Option<&str>) -> Arc<Locale> {
+        tag.and_then(|tag| self.get(tag))
+            .unwrap_or_else(|| self.default.clone())
+    }
+
+    pub(crate) fn get(&self, tag: &str) -> Option<Arc<Locale>> {
+        if tag.trim().is_empty() {
+            return None;
+        }
+        let locales = self.read();
         if let Some(code) = resolve_code(tag) {
-            if let Some(loc) = self.locales.get(&code) {
-                return Some(*loc);
+            if let Some(loc) = locales.get(&code) {
+                return Some(loc.clone());
             }
             if let Some(parsed) = parse_locale_tag(&code)
-                && let Some(loc) = self.locales.get(&parsed.language)
+                && let Some(loc) = locales.get(&parsed.language)
             {
-                return Some(*loc);
+                return Some(loc.clone());
             }
         }
-        if let Some(parsed) = parse_locale_tag(tag) {
-            if let Some(loc) = self.locales.get(&parsed.lang) {
-                return Some(*loc);
-            }
-            if let Some(loc) = self.locales.get(&parsed.language) {
-                return Some(*loc);
-            }
-        }
-        None
+        let parsed = parse_locale_tag(tag)?;
+        locales
+            .get(&parsed.lang)
+            .or_else(|| locales.get(&parsed.language))
+            .cloned()
     }
 
-    fn add_locale(&mut self, settings: LocaleSettings, tag: &str) -> Result<(), LocaleError> {
-        let parsed =
-            parse_locale_tag(tag).ok_or_else(|| LocaleError::InvalidTag(tag.to_string()))?;
-        let locale = leak_locale(settings.apply(self.default));
-        self.locales.insert(parsed.lang.clone(), locale);
-        if parsed.language != parsed.lang && !self.locales.contains_key(&parsed.language) {
-            self.locales.insert(parsed.language.clone(), locale);
-        }
-        Ok(())
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, Arc<Locale>>> {
+        self.locales
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, Arc<Locale>>> {
+        self.locales
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
     }
 }
 
-fn leak_locale(locale: Locale) -> &'static Locale {
-    Box::leak(Box::new(locale))
+impl Default for LocaleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for LocaleRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for LocaleRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocaleRegistry")
+            .field("tags", &self.tags())
+            .finish()
+    }
 }
 
 impl Locale {
@@ -284,12 +425,16 @@ impl Locale {
             ampm: ensure_pair(raw.ampm, ["AM", "PM"]),
             mmmm6: raw.mmmm6,
             mmm6: raw.mmm6,
+            mmmm8: raw.mmmm8,
+            mmm8: raw.mmm8,
             mmmm: raw.mmmm,
             mmm: raw.mmm,
             dddd: raw.dddd,
             ddd: raw.ddd,
             bool_values: ensure_pair(raw.bool_values, ["TRUE", "FALSE"]),
             prefer_mdy: raw.prefer_mdy,
+            native_digits: raw.digits.chars().collect::<Vec<_>>().try_into().ok(),
+            eras: raw.eras,
         }
     }
 }
@@ -1,3 +1,5 @@
+use super::{digits::Digits, exact::Exact, options::RoundingMode};
+
 pub fn round(number: f64, places: usize) -> f64 {
     if !number.is_finite() {
         return number;
@@ -12,6 +14,15 @@ pub fn round(number: f64, places: usize) -> f64 {
     number.round()
 }
 
+/// `round` under `mode`; other modes round the shortest decimal form of
+/// `number` so that binary noise does not push it across a boundary.
+pub fn round_mode(number: f64, places: usize, mode: RoundingMode) -> f64 {
+    if mode == RoundingMode::HalfAwayFromZero || !number.is_finite() {
+        return round(number, places);
+    }
+    Exact::from_f64(number).round(places, mode).to_f64()
+}
+
 pub fn clamp(number: f64) -> f64 {
     if number == 0.0 {
         return number;
@@ -142,20 +153,19 @@ pub fn numdec(value: f64, include_sign: bool) -> NumDecInfo {
         period_size = 1;
         let scale = 10_f64.powi(-int_size);
         let scaled = round(v * scale, 15);
-        let n = scaled.to_string();
-        let chars: Vec<char> = n.chars().collect();
-        let mut f = chars.len() as isize;
+        let n = Digits::display(scaled);
+        let mut f = n.len() as isize;
         let mut leading = true;
-        for ch in chars.iter() {
+        for ch in n.as_bytes() {
             match ch {
-                '.' => {
+                b'.' => {
                     f -= 1;
                     break;
                 }
-                '0' if leading => {
+                b'0' if leading => {
                     f -= 1;
                 }
-                '-' => {}
+                b'-' => {}
                 _ => {
                     leading = false;
                 }
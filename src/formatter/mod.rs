@@ -1,6 +1,5 @@
-use std::borrow::Cow;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex, OnceLock};
+use std::fmt::{self, Write};
+use std::sync::{Arc, OnceLock};
 
 use crate::constants::INDEX_COLORS;
 use crate::parser::model::{
@@ -9,26 +8,46 @@ use crate::parser::model::{
 use crate::parser::parse_pattern;
 use num_traits::{Signed, ToPrimitive};
 
+pub mod cache;
+mod compiled;
+mod context;
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+mod convert;
+mod digits;
 pub mod error;
+mod exact;
 mod general;
-mod locale;
+pub(crate) mod locale;
 mod math;
+mod numerals;
 pub mod options;
 mod pad;
+mod parts;
 mod run_part;
-mod serial;
+pub mod serial;
 mod to_ymd;
 pub mod value;
+mod zone;
 
+pub use cache::{CacheStats, clear_pattern_cache, pattern_cache_stats, set_pattern_cache_capacity};
+pub use compiled::CompiledFormat;
+pub use context::NumfmtContext;
 pub use error::FormatterError;
-pub use locale::{LocaleError, LocaleSettings, add_locale, default_locale};
-pub use options::FormatterOptions;
+pub use general::format_general_with_width;
+pub use locale::{
+    Era, LocaleError, LocaleRegistry, LocaleSettings, add_locale, default_locale, locale_tags,
+    remove_locale,
+};
+pub use options::{FormatterOptions, RoundingMode};
+pub use parts::{CellLayout, FormattedPart, PartKind};
 pub use run_part::RunValue;
 pub use value::{DateValue, FormatValue};
+pub use zone::TimeZone;
 
-use locale::get_locale_or_default;
+use cache::{CachedPattern, PatternCache};
+use parts::{PartKind as Role, Parts, Plain, Sink};
 use run_part::run_part;
-use serial::date_to_serial;
+use serial::wall_serial;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ColorValue {
@@ -36,25 +55,8 @@ pub enum ColorValue {
     Index(u32),
 }
 
-struct CacheEntry {
-    value: CachedPattern,
-}
-
-enum CachedPattern {
-    Valid(Arc<Pattern>),
-    Invalid {
-        message: String,
-        fallback: Arc<Pattern>,
-    },
-}
-
-static PATTERN_CACHE: OnceLock<Mutex<HashMap<String, CacheEntry>>> = OnceLock::new();
 static DEFAULT_TEXT_SECTION: OnceLock<Arc<Section>> = OnceLock::new();
 
-fn pattern_cache() -> &'static Mutex<HashMap<String, CacheEntry>> {
-    PATTERN_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
-}
-
 fn default_text_section() -> Arc<Section> {
     DEFAULT_TEXT_SECTION
         .get_or_init(|| {
@@ -83,16 +85,19 @@ fn build_error_pattern(pattern: &str, error: &str) -> Arc<Pattern> {
     })
 }
 
-fn prepare_pattern(pattern: &str, should_throw: bool) -> Result<Arc<Pattern>, FormatterError> {
-    let mut cache = pattern_cache().lock().expect("pattern cache poisoned");
-    if let Some(entry) = cache.get(pattern) {
-        return match &entry.value {
-            CachedPattern::Valid(pat) => Ok(pat.clone()),
+fn prepare_pattern(
+    cache: &PatternCache,
+    pattern: &str,
+    should_throw: bool,
+) -> Result<Arc<Pattern>, FormatterError> {
+    if let Some(cached) = cache.get(pattern) {
+        return match cached {
+            CachedPattern::Valid(pat) => Ok(pat),
             CachedPattern::Invalid { message, fallback } => {
                 if should_throw {
-                    Err(FormatterError::InvalidPattern(message.clone()))
+                    Err(FormatterError::InvalidPattern(message))
                 } else {
-                    Ok(fallback.clone())
+                    Ok(fallback)
                 }
             }
         };
@@ -101,24 +106,17 @@ fn prepare_pattern(pattern: &str, should_throw: bool) -> Result<Arc<Pattern>, Fo
     match parse_pattern(pattern) {
         Ok(parsed) => {
             let arc = Arc::new(parsed);
-            cache.insert(
-                pattern.to_string(),
-                CacheEntry {
-                    value: CachedPattern::Valid(arc.clone()),
-                },
-            );
+            cache.insert(pattern, CachedPattern::Valid(arc.clone()));
             Ok(arc)
         }
         Err(err) => {
             let message = err.to_string();
             let fallback = build_error_pattern(pattern, &message);
             cache.insert(
-                pattern.to_string(),
-                CacheEntry {
-                    value: CachedPattern::Invalid {
-                        message: message.clone(),
-                        fallback: fallback.clone(),
-                    },
+                pattern,
+                CachedPattern::Invalid {
+                    message,
+                    fallback: fallback.clone(),
                 },
             );
             if should_throw {
@@ -188,11 +186,6 @@ fn resolve_color_from_section(section: &Section, opts: &FormatterOptions) -> Opt
     }
 }
 
-fn locale_for(pattern: &Pattern, opts: &FormatterOptions) -> &'static locale::Locale {
-    let tag = resolve_locale_tag(pattern, opts);
-    get_locale_or_default(tag)
-}
-
 pub fn format<'a, V>(pattern: &str, value: V) -> Result<String, FormatterError>
 where
     V: Into<FormatValue<'a>>,
@@ -208,92 +201,230 @@ pub fn format_with_options<'a, V>(
 where
     V: Into<FormatValue<'a>>,
 {
-    let parse_data = prepare_pattern(pattern, options.throws)?;
-    let locale = locale_for(&parse_data, &options);
-    let parts = &parse_data.partitions;
+    NumfmtContext::global().format_with_options(pattern, value, &options)
+}
+
+/// Like [`format_with_options`], but writes into `out` instead of returning a
+/// new `String`. `out` may hold partial output when an error is returned.
+pub fn format_into<'a, W, V>(
+    out: &mut W,
+    pattern: &str,
+    value: V,
+    options: &FormatterOptions,
+) -> Result<(), FormatterError>
+where
+    W: Write + ?Sized,
+    V: Into<FormatValue<'a>>,
+{
+    NumfmtContext::global().write_value(out, pattern, &value.into(), options)
+}
+
+/// Formats like [`format_with_options`], split into runs of text tagged with
+/// the role they play, such as integer digits, group separators, signs or
+/// literals. Joining the texts gives the formatted string.
+///
+/// `*x` fills only produce [`PartKind::Fill`] parts when
+/// [`FormatterOptions::fill_char`] or [`FormatterOptions::width`] is set,
+/// since they write nothing otherwise.
+pub fn format_parts<'a, V>(
+    pattern: &str,
+    value: V,
+    options: FormatterOptions,
+) -> Result<Vec<FormattedPart>, FormatterError>
+where
+    V: Into<FormatValue<'a>>,
+{
+    NumfmtContext::global().parts(pattern, &value.into(), &options)
+}
+
+/// Formats into a [`CellLayout`] for callers that align the result in a cell
+/// themselves, such as spreadsheet grids drawing accounting formats.
+/// [`FormatterOptions::width`] narrows `General` and overflows as in
+/// [`format_with_options`], but the fill is left for the caller to repeat.
+pub fn format_layout<'a, V>(
+    pattern: &str,
+    value: V,
+    options: FormatterOptions,
+) -> Result<CellLayout, FormatterError>
+where
+    V: Into<FormatValue<'a>>,
+{
+    NumfmtContext::global().layout(pattern, &value.into(), &options)
+}
+
+/// [`Display`](fmt::Display) adapter over [`format_into`], for `write!` into
+/// any writer. Formatting errors surface as [`fmt::Error`].
+#[derive(Debug, Clone)]
+pub struct Formatted<'a> {
+    context: &'a NumfmtContext,
+    pattern: &'a str,
+    value: FormatValue<'a>,
+    options: &'a FormatterOptions,
+}
+
+impl<'a> Formatted<'a> {
+    pub fn new(
+        pattern: &'a str,
+        value: impl Into<FormatValue<'a>>,
+        options: &'a FormatterOptions,
+    ) -> Self {
+        Self::in_context(NumfmtContext::global(), pattern, value.into(), options)
+    }
+
+    fn in_context(
+        context: &'a NumfmtContext,
+        pattern: &'a str,
+        value: FormatValue<'a>,
+        options: &'a FormatterOptions,
+    ) -> Self {
+        Self {
+            context,
+            pattern,
+            value,
+            options,
+        }
+    }
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.context
+            .write_value(f, self.pattern, &self.value, self.options)
+            .map_err(|_| fmt::Error)
+    }
+}
+
+fn write_pattern<W: Write + ?Sized>(
+    out: &mut W,
+    pattern: &Pattern,
+    locale: &locale::Locale,
+    value: &FormatValue<'_>,
+    options: &FormatterOptions,
+) -> Result<(), FormatterError> {
+    emit_pattern(&mut Plain(out), pattern, locale, value, options)
+}
+
+fn pattern_parts(
+    pattern: &Pattern,
+    locale: &locale::Locale,
+    value: &FormatValue<'_>,
+    options: &FormatterOptions,
+) -> Result<Vec<FormattedPart>, FormatterError> {
+    let mut out = Parts::new();
+    emit_pattern(&mut out, pattern, locale, value, options)?;
+    Ok(out.into_parts())
+}
+
+fn pattern_layout(
+    pattern: &Pattern,
+    locale: &locale::Locale,
+    value: &FormatValue<'_>,
+    options: &FormatterOptions,
+) -> Result<CellLayout, FormatterError> {
+    let mut out = Parts::aligned();
+    emit_pattern(&mut out, pattern, locale, value, options)?;
+    Ok(CellLayout::from_parts(out.into_parts()))
+}
+
+fn emit_pattern<S: Sink + ?Sized>(
+    out: &mut S,
+    pattern: &Pattern,
+    locale: &locale::Locale,
+    value: &FormatValue<'_>,
+    options: &FormatterOptions,
+) -> Result<(), FormatterError> {
+    let parts = &pattern.partitions;
     let default_text = default_text_section();
     let text_section = parts.get(3).unwrap_or(default_text.as_ref());
 
-    let value = value.into();
     match value {
-        FormatValue::Null => Ok(String::new()),
+        FormatValue::Null => Ok(()),
         FormatValue::Boolean(flag) => {
-            let text = if flag {
-                locale.bool_true().to_string()
+            let text = if *flag {
+                locale.bool_true()
             } else {
-                locale.bool_false().to_string()
+                locale.bool_false()
             };
-            run_part(
-                run_part::RunValue::Text(Cow::Owned(text)),
-                text_section,
-                &options,
-                locale,
-            )
+            run_part(out, RunValue::from(text), text_section, options, locale)
         }
         FormatValue::Text(text) => run_part(
-            run_part::RunValue::Text(text),
+            out,
+            RunValue::from(text.as_ref()),
             text_section,
-            &options,
+            options,
             locale,
         ),
-        FormatValue::Number(num) => format_number(num, parts, &options, locale),
-        FormatValue::BigInt(big) => format_bigint(big, parts, &options, locale),
-        FormatValue::Date(date) => {
-            if let Some(serial) = date_to_serial(&date, options.ignore_timezone) {
-                format_number(serial, parts, &options, locale)
-            } else {
-                run_part(
-                    run_part::RunValue::Text(Cow::Owned("".to_string())),
-                    text_section,
-                    &options,
-                    locale,
-                )
+        FormatValue::Number(num) => format_number(out, *num, parts, options, locale),
+        FormatValue::BigInt(big) => format_bigint(out, big, parts, options, locale),
+        #[cfg(feature = "rust_decimal")]
+        FormatValue::Decimal(dec) => {
+            let condition_value = rust_decimal::prelude::ToPrimitive::to_f64(dec).unwrap_or(0.0);
+            match get_part(condition_value, parts) {
+                Some(section) => run_part(out, RunValue::Decimal(*dec), section, options, locale),
+                None => write_overflow(out, options),
             }
         }
+        FormatValue::Date(date) => {
+            let serial = wall_serial(date, options)?;
+            format_number(out, serial, parts, options, locale)
+        }
     }
 }
 
-fn format_number(
+fn format_number<S: Sink + ?Sized>(
+    out: &mut S,
     value: f64,
     parts: &[Section],
     options: &FormatterOptions,
     locale: &locale::Locale,
-) -> Result<String, FormatterError> {
+) -> Result<(), FormatterError> {
     if !value.is_finite() {
         if value.is_nan() {
-            return Ok(locale.nan.clone());
+            out.role(Role::Nan);
+            out.write_str(&locale.nan)?;
+            return Ok(());
         }
-        let mut result = String::new();
         if value.is_sign_negative() {
-            result.push_str(&locale.negative);
+            out.role(Role::MinusSign);
+            out.write_str(&locale.negative)?;
         }
-        result.push_str(&locale.infinity);
-        return Ok(result);
+        out.role(Role::Infinity);
+        out.write_str(&locale.infinity)?;
+        return Ok(());
     }
 
     let part = get_part(value, parts);
     if let Some(section) = part {
-        run_part(run_part::RunValue::Number(value), section, options, locale)
+        run_part(out, RunValue::Number(value), section, options, locale)
     } else {
-        Ok(options.overflow.clone())
+        write_overflow(out, options)
     }
 }
 
-fn format_bigint(
-    value: num_bigint::BigInt,
+fn format_bigint<S: Sink + ?Sized>(
+    out: &mut S,
+    value: &num_bigint::BigInt,
     parts: &[Section],
     options: &FormatterOptions,
     locale: &locale::Locale,
-) -> Result<String, FormatterError> {
-    let condition_value = bigint_condition_value(&value);
+) -> Result<(), FormatterError> {
+    let condition_value = bigint_condition_value(value);
     let part = get_part(condition_value, parts);
     if let Some(section) = part {
-        run_part(run_part::RunValue::BigInt(&value), section, options, locale)
+        run_part(out, RunValue::BigInt(value), section, options, locale)
     } else {
-        Ok(options.overflow.clone())
+        write_overflow(out, options)
     }
 }
 
+fn write_overflow<S: Sink + ?Sized>(
+    out: &mut S,
+    options: &FormatterOptions,
+) -> Result<(), FormatterError> {
+    out.role(Role::Overflow);
+    Ok(out.write_str(&options.overflow)?)
+}
+
 pub fn format_color<'a, V>(
     pattern: &str,
     value: V,
@@ -302,13 +433,19 @@ pub fn format_color<'a, V>(
 where
     V: Into<FormatValue<'a>>,
 {
-    let value = value.into();
-    let parse_data = prepare_pattern(pattern, options.throws)?;
-    let parts = &parse_data.partitions;
+    NumfmtContext::global().color(pattern, &value.into(), &options)
+}
+
+fn pattern_color(
+    pattern: &Pattern,
+    value: &FormatValue<'_>,
+    options: &FormatterOptions,
+) -> Option<ColorValue> {
+    let parts = &pattern.partitions;
     let default_text = default_text_section();
     let mut part: Option<&Section> = parts.get(3).or_else(|| Some(default_text.as_ref()));
 
-    match &value {
+    match value {
         FormatValue::Number(num) if num.is_finite() => {
             part = get_part(*num, parts);
         }
@@ -316,13 +453,13 @@ where
             let num = bigint_condition_value(big);
             part = get_part(num, parts);
         }
+        #[cfg(feature = "rust_decimal")]
+        FormatValue::Decimal(dec) => {
+            let num = rust_decimal::prelude::ToPrimitive::to_f64(dec).unwrap_or(0.0);
+            part = get_part(num, parts);
+        }
         _ => {}
     }
 
-    let section = match part {
-        Some(section) => section,
-        None => return Ok(None),
-    };
-
-    Ok(resolve_color_from_section(section, &options))
+    resolve_color_from_section(part?, options)
 }
@@ -0,0 +1,393 @@
+use crate::parser::Numerals;
+use crate::parser::model::{DateTokenKind, NumberPart, SectionToken, TokenKind};
+
+use super::locale::{Locale, resolve_locale};
+use super::options::FormatterOptions;
+
+/// How `1` is written in front of a positional unit such as 十 or 百.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OneRule {
+    Keep,
+    LeadingTen,
+    Omit,
+}
+
+pub(crate) struct Table {
+    digits: [&'static str; 10],
+    /// Written for a run of zeros between two significant digits.
+    zero: &'static str,
+    units: [&'static str; 3],
+    myriads: [&'static str; 3],
+    one: OneRule,
+}
+
+const ZH_LOWER: Table = Table {
+    digits: ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"],
+    zero: "〇",
+    units: ["十", "百", "千"],
+    myriads: ["万", "亿", "兆"],
+    one: OneRule::LeadingTen,
+};
+
+const ZH_UPPER: Table = Table {
+    digits: ["零", "壹", "贰", "叁", "肆", "伍", "陆", "柒", "捌", "玖"],
+    zero: "零",
+    units: ["拾", "佰", "仟"],
+    myriads: ["万", "亿", "兆"],
+    one: OneRule::Keep,
+};
+
+const ZH_FULLWIDTH: Table = Table {
+    digits: ["０", "１", "２", "３", "４", "５", "６", "７", "８", "９"],
+    zero: "０",
+    units: ["十", "百", "千"],
+    myriads: ["万", "亿", "兆"],
+    one: OneRule::Keep,
+};
+
+const TW_LOWER: Table = Table {
+    myriads: ["萬", "億", "兆"],
+    ..ZH_LOWER
+};
+
+const TW_UPPER: Table = Table {
+    digits: ["零", "壹", "貳", "參", "肆", "伍", "陸", "柒", "捌", "玖"],
+    zero: "零",
+    units: ["拾", "佰", "仟"],
+    myriads: ["萬", "億", "兆"],
+    one: OneRule::Keep,
+};
+
+const TW_FULLWIDTH: Table = Table {
+    myriads: ["萬", "億", "兆"],
+    ..ZH_FULLWIDTH
+};
+
+const JA_LOWER: Table = Table {
+    digits: ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"],
+    zero: "",
+    units: ["十", "百", "千"],
+    myriads: ["万", "億", "兆"],
+    one: OneRule::Omit,
+};
+
+const JA_UPPER: Table = Table {
+    digits: ["〇", "壱", "弐", "参", "四", "五", "六", "七", "八", "九"],
+    zero: "",
+    units: ["拾", "百", "阡"],
+    myriads: ["萬", "億", "兆"],
+    one: OneRule::Keep,
+};
+
+const JA_FULLWIDTH: Table = Table {
+    digits: ["０", "１", "２", "３", "４", "５", "６", "７", "８", "９"],
+    zero: "",
+    units: ["十", "百", "千"],
+    myriads: ["万", "億", "兆"],
+    one: OneRule::Keep,
+};
+
+const KO_LOWER: Table = Table {
+    digits: ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"],
+    zero: "",
+    units: ["十", "百", "千"],
+    myriads: ["萬", "億", "兆"],
+    one: OneRule::Omit,
+};
+
+const KO_UPPER: Table = Table {
+    digits: ["零", "壹", "貳", "參", "四", "五", "六", "七", "八", "九"],
+    zero: "",
+    units: ["拾", "百", "阡"],
+    myriads: ["萬", "億", "兆"],
+    one: OneRule::Keep,
+};
+
+const KO_FULLWIDTH: Table = Table {
+    myriads: ["萬", "億", "兆"],
+    ..JA_FULLWIDTH
+};
+
+const KO_HANGUL: Table = Table {
+    digits: ["영", "일", "이", "삼", "사", "오", "육", "칠", "팔", "구"],
+    zero: "",
+    units: ["십", "백", "천"],
+    myriads: ["만", "억", "조"],
+    one: OneRule::Omit,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Chinese,
+    Traditional,
+    Japanese,
+    Korean,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Lower,
+    Upper,
+    FullWidth,
+    Hangul,
+}
+
+/// Zero digit of each numeral script addressed by the top byte of a locale
+/// code; the other nine digits follow it contiguously.
+const SCRIPTS: [(u8, char); 18] = [
+    (0x01, '0'),
+    (0x02, '\u{0660}'),
+    (0x03, '\u{06F0}'),
+    (0x04, '\u{0966}'),
+    (0x05, '\u{09E6}'),
+    (0x06, '\u{0A66}'),
+    (0x07, '\u{0AE6}'),
+    (0x08, '\u{0B66}'),
+    (0x09, '\u{0BE6}'),
+    (0x0A, '\u{0C66}'),
+    (0x0B, '\u{0CE6}'),
+    (0x0C, '\u{0D66}'),
+    (0x0D, '\u{0E50}'),
+    (0x0E, '\u{0ED0}'),
+    (0x0F, '\u{0F20}'),
+    (0x10, '\u{1040}'),
+    (0x12, '\u{17E0}'),
+    (0x13, '\u{1810}'),
+];
+
+/// A resolved numeral substitution, ready to rewrite ASCII digits.
+pub(crate) enum NumeralSystem {
+    Ideographic {
+        table: &'static Table,
+        positional: bool,
+        short: bool,
+    },
+    Script([char; 10]),
+}
+
+impl NumeralSystem {
+    /// Picks the substitution for a section: its own modifier first, then the
+    /// locale's native digits when `native_digits` is enabled.
+    pub(crate) fn for_section(
+        numerals: Option<Numerals>,
+        tag: Option<&str>,
+        opts: &FormatterOptions,
+        locale: &Locale,
+    ) -> Option<Self> {
+        match numerals {
+            Some(numerals) => Self::resolve(numerals, tag),
+            None if opts.native_digits => locale.native_digits.map(Self::Script),
+            None => None,
+        }
+    }
+
+    /// Resolves a modifier against a locale tag (`804`, `ja-JP`, ...), falling
+    /// back to Simplified Chinese. Returns `None` for unsupported variants.
+    pub(crate) fn resolve(numerals: Numerals, tag: Option<&str>) -> Option<Self> {
+        if let Numerals::Script(code) = numerals {
+            let (_, zero) = SCRIPTS.iter().find(|(script, _)| *script == code)?;
+            let digits = std::array::from_fn(|i| {
+                char::from_u32(*zero as u32 + i as u32).expect("contiguous digit block")
+            });
+            return Some(Self::Script(digits));
+        }
+
+        let language = match tag.and_then(resolve_locale).as_deref() {
+            Some(lang) if lang.starts_with("ja") => Language::Japanese,
+            Some(lang) if lang.starts_with("ko") => Language::Korean,
+            Some("zh_TW" | "zh_HK" | "zh_MO") => Language::Traditional,
+            _ => Language::Chinese,
+        };
+
+        let (style, positional, short) = match numerals {
+            Numerals::DbNum(1) => (Style::Lower, true, false),
+            Numerals::DbNum(2) => (Style::Upper, true, false),
+            Numerals::DbNum(3) if language == Language::Japanese => {
+                (Style::FullWidth, false, false)
+            }
+            Numerals::DbNum(3) => (Style::FullWidth, true, false),
+            Numerals::DbNum(4) if language == Language::Korean => (Style::Hangul, true, false),
+            Numerals::NatNum(1) => (Style::Lower, false, false),
+            Numerals::NatNum(2) => (Style::Upper, false, false),
+            Numerals::NatNum(3) => (Style::FullWidth, false, false),
+            Numerals::NatNum(4) => (Style::Lower, true, false),
+            Numerals::NatNum(5) => (Style::Upper, true, false),
+            Numerals::NatNum(6) => (Style::FullWidth, true, false),
+            Numerals::NatNum(7) => (Style::Lower, true, true),
+            Numerals::NatNum(8) => (Style::Upper, true, true),
+            Numerals::NatNum(9) if language == Language::Korean => (Style::Hangul, false, false),
+            Numerals::NatNum(10) if language == Language::Korean => (Style::Hangul, true, false),
+            Numerals::NatNum(11) if language == Language::Korean => (Style::Hangul, true, true),
+            _ => return None,
+        };
+
+        let table = match (language, style) {
+            (_, Style::Hangul) => &KO_HANGUL,
+            (Language::Chinese, Style::Lower) => &ZH_LOWER,
+            (Language::Chinese, Style::Upper) => &ZH_UPPER,
+            (Language::Chinese, Style::FullWidth) => &ZH_FULLWIDTH,
+            (Language::Traditional, Style::Lower) => &TW_LOWER,
+            (Language::Traditional, Style::Upper) => &TW_UPPER,
+            (Language::Traditional, Style::FullWidth) => &TW_FULLWIDTH,
+            (Language::Japanese, Style::Lower) => &JA_LOWER,
+            (Language::Japanese, Style::Upper) => &JA_UPPER,
+            (Language::Japanese, Style::FullWidth) => &JA_FULLWIDTH,
+            (Language::Korean, Style::Lower) => &KO_LOWER,
+            (Language::Korean, Style::Upper) => &KO_UPPER,
+            (Language::Korean, Style::FullWidth) => &KO_FULLWIDTH,
+        };
+
+        Some(Self::Ideographic {
+            table,
+            positional,
+            short,
+        })
+    }
+
+    /// Rewrites the digits a token produced. Integer-like runs are spelled
+    /// positionally when the system allows it; digits after `decimal` and
+    /// digit-wise tokens (years, fractions) are transliterated one by one.
+    pub(crate) fn convert(
+        &self,
+        text: &str,
+        digitwise: bool,
+        decimal: &str,
+        group: &str,
+    ) -> String {
+        let positional = !digitwise
+            && matches!(
+                self,
+                Self::Ideographic {
+                    positional: true,
+                    ..
+                }
+            );
+        let mut out = String::with_capacity(text.len() * 3);
+        let mut after_decimal = false;
+        let mut rest = text;
+
+        while let Some(ch) = rest.chars().next() {
+            if ch.is_ascii_digit() {
+                let mut run = String::new();
+                loop {
+                    let len = rest
+                        .find(|c: char| !c.is_ascii_digit())
+                        .unwrap_or(rest.len());
+                    run.push_str(&rest[..len]);
+                    rest = &rest[len..];
+                    // positional forms absorb the thousands separators
+                    if positional
+                        && !after_decimal
+                        && !group.is_empty()
+                        && rest
+                            .strip_prefix(group)
+                            .is_some_and(|tail| tail.starts_with(|c: char| c.is_ascii_digit()))
+                    {
+                        rest = &rest[group.len()..];
+                        continue;
+                    }
+                    break;
+                }
+                if positional && !after_decimal {
+                    self.push_positional(&mut out, &run);
+                } else {
+                    self.push_digits(&mut out, &run);
+                }
+            } else if !decimal.is_empty() && rest.starts_with(decimal) {
+                after_decimal = true;
+                out.push_str(decimal);
+                rest = &rest[decimal.len()..];
+            } else {
+                out.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+
+        out
+    }
+
+    fn push_digits(&self, out: &mut String, run: &str) {
+        for b in run.bytes() {
+            let digit = (b - b'0') as usize;
+            match self {
+                Self::Ideographic { table, .. } => out.push_str(table.digits[digit]),
+                Self::Script(digits) => out.push(digits[digit]),
+            }
+        }
+    }
+
+    fn push_positional(&self, out: &mut String, run: &str) {
+        let Self::Ideographic { table, short, .. } = self else {
+            self.push_digits(out, run);
+            return;
+        };
+        let digits = run.trim_start_matches('0');
+        if digits.is_empty() {
+            out.push_str(table.digits[0]);
+            return;
+        }
+        // beyond 兆 there is no shared unit across the supported locales
+        if digits.len() > 16 {
+            self.push_digits(out, digits);
+            return;
+        }
+
+        let one = if *short { OneRule::Omit } else { table.one };
+        let start = out.len();
+        let len = digits.len();
+        let mut pending_zero = false;
+        let mut group_has_digit = false;
+
+        for (i, b) in digits.bytes().enumerate() {
+            let digit = (b - b'0') as usize;
+            let power = len - 1 - i;
+            let unit = power % 4;
+            if digit == 0 {
+                pending_zero = true;
+            } else {
+                if pending_zero {
+                    out.push_str(table.zero);
+                    pending_zero = false;
+                }
+                let omit_one = digit == 1
+                    && unit > 0
+                    && match one {
+                        OneRule::Keep => false,
+                        OneRule::LeadingTen => unit == 1 && out.len() == start,
+                        OneRule::Omit => true,
+                    };
+                if !omit_one {
+                    out.push_str(table.digits[digit]);
+                }
+                if unit > 0 {
+                    out.push_str(table.units[unit - 1]);
+                }
+                group_has_digit = true;
+            }
+            if unit == 0 && power > 0 {
+                if group_has_digit {
+                    out.push_str(table.myriads[power / 4 - 1]);
+                }
+                group_has_digit = false;
+            }
+        }
+    }
+}
+
+/// Whether a token's digits are rewritten and, if so, whether they are spelled
+/// digit by digit instead of positionally.
+pub(crate) fn digitwise(token: &SectionToken) -> Option<bool> {
+    match token {
+        SectionToken::Number(number) => Some(number.part == NumberPart::Fraction),
+        SectionToken::Date(date) => Some(matches!(
+            date.kind,
+            DateTokenKind::Year
+                | DateTokenKind::YearShort
+                | DateTokenKind::BuddhistYear
+                | DateTokenKind::BuddhistYearShort
+                | DateTokenKind::Subsecond
+        )),
+        SectionToken::Token(token) if token.kind == TokenKind::General => Some(false),
+        _ => None,
+    }
+}
@@ -1,20 +1,69 @@
+use std::sync::Arc;
+
+use super::locale::LocaleRegistry;
+use super::zone::TimeZone;
+
+/// How digits beyond a pattern's precision are rounded away.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Ties move away from zero, as in Excel.
+    #[default]
+    HalfAwayFromZero,
+    /// Ties move to the even neighbour (banker's rounding).
+    HalfEven,
+    /// Drops the extra digits.
+    Truncate,
+    /// Rounds toward negative infinity.
+    Floor,
+    /// Rounds toward positive infinity.
+    Ceiling,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct FormatterOptions {
     pub overflow: String,
     pub date_error_throws: bool,
     pub date_error_number: bool,
     pub bigint_error_number: bool,
+    /// Format BigInt values from their digits rather than through `f64`, so
+    /// values beyond ±2^53 keep their precision instead of overflowing.
+    pub bigint_exact: bool,
     pub date_span_large: bool,
     pub leap_1900: bool,
     pub nbsp: bool,
     pub throws: bool,
     pub invalid: String,
     pub locale: String,
+    /// Registry that `locale` and pattern locales are looked up in; the
+    /// process-wide one when unset.
+    pub locales: Option<Arc<LocaleRegistry>>,
+    /// Format zoned dates as plain wall-clock values, skipping conversion.
     pub ignore_timezone: bool,
+    /// Zone that zoned dates are converted into; UTC when unset, so a value
+    /// carrying another offset prints a different wall-clock time than it
+    /// was built with unless `ignore_timezone` is set.
+    pub timezone: Option<TimeZone>,
     pub grouping: Vec<u8>,
     pub index_colors: bool,
     pub skip_char: Option<String>,
     pub fill_char: Option<String>,
+    /// Column width in terminal cells, with East Asian wide characters taking
+    /// two. A `*x` fill repeats its character up to it and `_x` skips pad by
+    /// the width of `x`; takes precedence over `fill_char`. Numbers that do
+    /// not fit narrow `General` like Excel does, or else become `overflow`.
+    pub width: Option<usize>,
+    /// Cells that every `General` token narrows its number to, including a
+    /// negative sign written with it, as
+    /// [`format_general_with_width`](super::format_general_with_width) does.
+    /// Numbers that cannot fit keep the usual General form.
+    pub general_width: Option<usize>,
+    /// Render digits in the locale's native script when it defines one.
+    pub native_digits: bool,
+    /// Interpret serials against the 1904 date system used by Mac workbooks.
+    pub date_1904: bool,
+    /// How ties round for `0` and `#` placeholders; `HalfAwayFromZero` by
+    /// default, as in Excel.
+    pub rounding: RoundingMode,
 }
 
 impl Default for FormatterOptions {
@@ -24,17 +73,25 @@ impl Default for FormatterOptions {
             date_error_throws: false,
             date_error_number: true,
             bigint_error_number: false,
+            bigint_exact: false,
             date_span_large: true,
             leap_1900: true,
             nbsp: false,
             throws: true,
             invalid: "######".to_string(),
             locale: String::new(),
+            locales: None,
             ignore_timezone: false,
+            timezone: None,
             grouping: vec![3, 3],
             index_colors: true,
             skip_char: None,
             fill_char: None,
+            width: None,
+            general_width: None,
+            native_digits: false,
+            date_1904: false,
+            rounding: RoundingMode::HalfAwayFromZero,
         }
     }
 }
@@ -45,6 +102,11 @@ impl FormatterOptions {
         self
     }
 
+    pub fn with_locales(mut self, registry: Arc<LocaleRegistry>) -> Self {
+        self.locales = Some(registry);
+        self
+    }
+
     pub fn with_nbsp(mut self, nbsp: bool) -> Self {
         self.nbsp = nbsp;
         self
@@ -67,4 +129,44 @@ impl FormatterOptions {
         self.fill_char = ch;
         self
     }
+
+    pub fn with_width(mut self, width: Option<usize>) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn with_general_width(mut self, general_width: Option<usize>) -> Self {
+        self.general_width = general_width;
+        self
+    }
+
+    pub fn with_native_digits(mut self, native_digits: bool) -> Self {
+        self.native_digits = native_digits;
+        self
+    }
+
+    pub fn with_date_1904(mut self, date_1904: bool) -> Self {
+        self.date_1904 = date_1904;
+        self
+    }
+
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn with_bigint_exact(mut self, exact: bool) -> Self {
+        self.bigint_exact = exact;
+        self
+    }
+
+    pub fn with_timezone(mut self, zone: TimeZone) -> Self {
+        self.timezone = Some(zone);
+        self
+    }
+
+    pub fn with_ignore_timezone(mut self, ignore: bool) -> Self {
+        self.ignore_timezone = ignore;
+        self
+    }
 }
@@ -0,0 +1,218 @@
+use std::fmt::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+/// Role of a run of formatted text, in the spirit of
+/// `Intl.NumberFormat.prototype.formatToParts`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartKind {
+    /// Integer digits, including `?` and `#` padding.
+    Integer,
+    /// Thousands separator.
+    Group,
+    /// Decimal separator.
+    Decimal,
+    /// Decimal fraction digits.
+    Fraction,
+    MinusSign,
+    PlusSign,
+    Percent,
+    /// `E` of scientific notation.
+    ExponentSeparator,
+    ExponentSign,
+    ExponentInteger,
+    Numerator,
+    /// `/` between numerator and denominator, or the padding standing in for it.
+    FractionSlash,
+    Denominator,
+    /// Number written by the `General` format.
+    General,
+    /// Text value of a `@` placeholder.
+    Text,
+    /// Repeated character of a `*x` fill.
+    Fill,
+    /// Padding of a `_x` skip.
+    Skip,
+    Currency,
+    /// Quoted, escaped or bare literal text.
+    Literal,
+    Year,
+    Era,
+    Month,
+    Day,
+    Weekday,
+    Hour,
+    Minute,
+    Second,
+    FractionalSecond,
+    /// AM/PM marker.
+    DayPeriod,
+    Nan,
+    Infinity,
+    /// The overflow marker written when no section applies or a date is out of range.
+    Overflow,
+    /// The invalid marker written for a pattern that failed to parse.
+    Invalid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedPart {
+    pub kind: PartKind,
+    pub text: String,
+}
+
+/// How the parts of a formatted value sit in a cell: the prefix against the
+/// left edge, the suffix against the right edge and the fill repeated in
+/// between, as accounting formats such as `_-* #,##0.00_-` lay out.
+///
+/// Skips at either edge, or right after a leading minus sign, are given as
+/// widths in terminal cells instead of parts. Without a fill, every part is
+/// in the prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CellLayout {
+    pub leading_skip: usize,
+    pub prefix: Vec<FormattedPart>,
+    /// Character of the `*x` fill, repeated to fill the cell.
+    pub fill: Option<String>,
+    pub suffix: Vec<FormattedPart>,
+    pub trailing_skip: usize,
+}
+
+impl CellLayout {
+    pub(crate) fn from_parts(mut parts: Vec<FormattedPart>) -> Self {
+        let (mut prefix, fill, mut suffix) =
+            match parts.iter().position(|part| part.kind == PartKind::Fill) {
+                Some(at) => {
+                    let suffix = parts.split_off(at + 1);
+                    let fill = parts.pop().map(|part| part.text);
+                    (parts, fill, suffix)
+                }
+                None => (parts, None, Vec::new()),
+            };
+        // the minus sign of a single-section format comes before its skip
+        let at = usize::from(
+            prefix
+                .first()
+                .is_some_and(|p| p.kind == PartKind::MinusSign),
+        );
+        let leading_skip = match prefix.get(at) {
+            Some(part) if part.kind == PartKind::Skip => prefix.remove(at).text.width(),
+            _ => 0,
+        };
+        let last = if fill.is_some() {
+            &mut suffix
+        } else {
+            &mut prefix
+        };
+        let trailing_skip = match last.last() {
+            Some(part) if part.kind == PartKind::Skip => last.pop().map_or(0, |p| p.text.width()),
+            _ => 0,
+        };
+        Self {
+            leading_skip,
+            prefix,
+            fill,
+            suffix,
+            trailing_skip,
+        }
+    }
+
+    /// Narrowest cell that holds everything but the fill.
+    pub fn min_width(&self) -> usize {
+        let text: usize = self
+            .prefix
+            .iter()
+            .chain(&self.suffix)
+            .map(|part| part.text.width())
+            .sum();
+        self.leading_skip + text + self.trailing_skip
+    }
+}
+
+/// Output that is told the role of the text written next. Plain writers
+/// ignore it.
+pub(crate) trait Sink: Write {
+    fn role(&mut self, kind: PartKind);
+
+    /// Whether the output is aligned afterwards, so that a fill writes its
+    /// character once as a marker and skips pad by the width of theirs.
+    fn aligns(&self) -> bool {
+        false
+    }
+}
+
+/// Adapts any writer to a [`Sink`] that drops roles.
+pub(crate) struct Plain<'a, W: ?Sized>(pub &'a mut W);
+
+impl<W: Write + ?Sized> Write for Plain<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.0.write_char(c)
+    }
+}
+
+impl<W: Write + ?Sized> Sink for Plain<'_, W> {
+    fn role(&mut self, _: PartKind) {}
+}
+
+impl Sink for String {
+    fn role(&mut self, _: PartKind) {}
+}
+
+/// Collects output into parts, merging adjacent text of the same role.
+pub(crate) struct Parts {
+    parts: Vec<FormattedPart>,
+    kind: PartKind,
+    aligned: bool,
+}
+
+impl Parts {
+    pub fn new() -> Self {
+        Self {
+            parts: Vec::new(),
+            kind: PartKind::Literal,
+            aligned: false,
+        }
+    }
+
+    /// Parts that are laid out in a cell afterwards; see [`Sink::aligns`].
+    pub fn aligned() -> Self {
+        Self {
+            aligned: true,
+            ..Self::new()
+        }
+    }
+
+    pub fn into_parts(self) -> Vec<FormattedPart> {
+        self.parts
+    }
+}
+
+impl Write for Parts {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+        match self.parts.last_mut() {
+            Some(last) if last.kind == self.kind => last.text.push_str(s),
+            _ => self.parts.push(FormattedPart {
+                kind: self.kind,
+                text: s.to_string(),
+            }),
+        }
+        Ok(())
+    }
+}
+
+impl Sink for Parts {
+    fn role(&mut self, kind: PartKind) {
+        self.kind = kind;
+    }
+
+    fn aligns(&self) -> bool {
+        self.aligned
+    }
+}
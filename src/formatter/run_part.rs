@@ -1,23 +1,33 @@
 use std::borrow::Cow;
+use std::fmt::{self, Write};
 use std::ptr;
 
 use num_bigint::BigInt;
 use num_traits::ToPrimitive;
+use unicode_width::UnicodeWidthStr;
 
-use crate::constants::{DateUnits, MAX_L_DATE, MAX_S_DATE, MIN_L_DATE, MIN_S_DATE};
+use crate::constants::{
+    DateUnits, EPOCH_1317, EPOCH_1900, EPOCH_1904_OFFSET, EPOCH_HEBREW, EPOCH_JAPANESE,
+    EPOCH_TAIWAN, MAX_L_DATE, MAX_S_DATE, MIN_L_DATE, MIN_S_DATE,
+};
 use crate::parser::model::{
-    DateToken, DateTokenKind, NumberPart, NumberToken, Section, SectionToken, StringRule, Token,
-    TokenKind,
+    DateToken, DateTokenKind, NumberPart, NumberToken, Section, SectionToken, StringRole,
+    StringRule, Token, TokenKind,
 };
 
 use super::{
+    digits::Digits,
     error::FormatterError,
-    general::format_general,
+    exact::Exact,
+    general::{format_general, format_general_exact, format_general_fit},
     locale::{Locale, default_locale},
-    math::{clamp, dec2frac, get_exponent, get_significand, round},
-    options::FormatterOptions,
+    math::{clamp, dec2frac, get_exponent, get_significand, round, round_mode},
+    numerals::{NumeralSystem, digitwise},
+    options::{FormatterOptions, RoundingMode},
     pad::pad,
+    parts::{FormattedPart, PartKind, Parts, Sink},
     serial::date_from_serial,
+    to_ymd::{hebrew_month_index, to_ymd},
 };
 
 const DAYSIZE: f64 = 86_400.0;
@@ -28,6 +38,8 @@ const MIN_SAFE_INTEGER: i128 = -9_007_199_254_740_991;
 pub enum RunValue<'a> {
     Number(f64),
     BigInt(&'a BigInt),
+    #[cfg(feature = "rust_decimal")]
+    Decimal(rust_decimal::Decimal),
     Text(Cow<'a, str>),
 }
 
@@ -43,6 +55,13 @@ impl<'a> From<&'a BigInt> for RunValue<'a> {
     }
 }
 
+#[cfg(feature = "rust_decimal")]
+impl<'a> From<rust_decimal::Decimal> for RunValue<'a> {
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Self::Decimal(value)
+    }
+}
+
 impl<'a> From<&'a str> for RunValue<'a> {
     fn from(value: &'a str) -> Self {
         Self::Text(Cow::Borrowed(value))
@@ -55,60 +74,107 @@ impl<'a> From<String> for RunValue<'a> {
     }
 }
 
-pub fn run_part(
+pub(crate) fn run_part<S: Sink + ?Sized>(
+    out: &mut S,
     value: RunValue<'_>,
     part: &Section,
     opts: &FormatterOptions,
     locale: &Locale,
-) -> Result<String, FormatterError> {
+) -> Result<(), FormatterError> {
+    let numerals = NumeralSystem::for_section(
+        part.numerals,
+        part.locale.as_deref().or(Some(&opts.locale)),
+        opts,
+        locale,
+    );
+    let transliterate = |text: String| match &numerals {
+        Some(system) => system.convert(&text, false, &locale.decimal, &locale.group),
+        None => text,
+    };
+
     let mut numeric_value = match value {
         RunValue::Number(n) => Some(n),
         RunValue::BigInt(big) => {
             if BigInt::from(MIN_SAFE_INTEGER) <= *big && *big <= BigInt::from(MAX_SAFE_INTEGER) {
                 big.to_f64()
+            } else if opts.bigint_exact && part.date.is_empty() {
+                Some(Exact::from(big).to_f64())
+            } else if opts.bigint_error_number {
+                out.role(PartKind::General);
+                out.write_str(&transliterate(big.to_string()))?;
+                return Ok(());
             } else {
-                return Ok(if opts.bigint_error_number {
-                    big.to_string()
-                } else {
-                    opts.overflow.clone()
-                });
+                out.role(PartKind::Overflow);
+                out.write_str(&opts.overflow)?;
+                return Ok(());
             }
         }
+        #[cfg(feature = "rust_decimal")]
+        RunValue::Decimal(dec) => rust_decimal::prelude::ToPrimitive::to_f64(&dec),
         RunValue::Text(_) => None,
     };
+    // Exact digits for values that would lose precision as f64
+    let mut exact: Option<Exact> = match &value {
+        #[cfg(feature = "rust_decimal")]
+        RunValue::Decimal(dec) => Some(Exact::from(*dec)),
+        RunValue::BigInt(big) if opts.bigint_exact && part.date.is_empty() => {
+            Some(Exact::from(*big))
+        }
+        _ => None,
+    };
+    // Other rounding modes work on the decimal digits of the value
+    if exact.is_none()
+        && opts.rounding != RoundingMode::HalfAwayFromZero
+        && part.date.is_empty()
+        && !part.fractions
+        && let Some(num) = numeric_value.filter(|n| n.is_finite())
+    {
+        exact = Some(Exact::from_f64(num));
+    }
 
     let text_value = match &value {
         RunValue::Text(cow) => Some(cow.as_ref()),
         _ => None,
     };
 
-    let mut mantissa = String::new();
-    let mut mantissa_sign = String::new();
-    let mut numerator = String::new();
-    let mut denominator = String::new();
-    let mut fraction = String::new();
-    let mut integer = String::new();
+    let mut mantissa = Digits::new();
+    let mut numerator = Digits::new();
+    let mut denominator = Digits::new();
+    let mut fraction = Digits::new();
+    let mut integer = Digits::new();
     let mut exponent = 0i32;
-    let mut date = numeric_value.map(|n| n.trunc()).unwrap_or(0.0);
-    let mut time = 0.0;
-    let mut year = 0i32;
-    let mut month = 1u8;
-    let mut day = 0i32;
-    let mut weekday = 0usize;
-    let mut hour = 0i32;
-    let mut minute = 0i32;
-    let mut second = 0i32;
-    let mut subsec = 0.0f64;
+    let mut clock = Clock {
+        month: 1,
+        civil: [0, 1, 0],
+        date: numeric_value.map(|n| n.trunc()).unwrap_or(0.0),
+        ..Clock::default()
+    };
 
     if !part.text
         && part.scale.is_finite()
         && (part.scale - 1.0).abs() > f64::EPSILON
         && let Some(num) = numeric_value
     {
-        numeric_value = Some(clamp(num * part.scale));
+        exact = exact.and_then(|exact| {
+            let places = part.scale.log10().round() as i32;
+            (10_f64.powi(places) == part.scale).then(|| exact.shift(places))
+        });
+        numeric_value = Some(match &exact {
+            Some(exact) => exact.to_f64(),
+            None => clamp(num * part.scale),
+        });
     }
 
     if part.exponential
+        && let Some(significand) = &exact
+    {
+        let (significand, exp) =
+            significand.scientific(part.int_max, part.integer, part.frac_max, opts.rounding);
+        exponent = exp;
+        numeric_value = Some(significand.to_f64());
+        exact = Some(significand);
+        write!(mantissa, "{}", exponent.abs())?;
+    } else if part.exponential
         && let Some(mut val) = numeric_value
     {
         let mut abs_val = val.abs();
@@ -125,51 +191,55 @@ pub fn run_part(
         }
         val = if val < 0.0 { -abs_val } else { abs_val };
         numeric_value = Some(val);
-        mantissa = exponent.abs().to_string();
+        write!(mantissa, "{}", exponent.abs())?;
     }
 
     if part.integer
         && let Some(num) = numeric_value
     {
-        let rounded = round(num, if part.fractions { 1 } else { part.frac_max });
-        let abs_rounded = rounded.abs();
-        if abs_rounded >= 1.0 {
-            integer = abs_rounded.floor().to_string();
+        let places = if part.fractions { 1 } else { part.frac_max };
+        if let Some(exact) = &exact {
+            integer.push_str(&exact.round(places, opts.rounding).integer());
+        } else {
+            let abs_rounded = round(num, places).abs();
+            if abs_rounded >= 1.0 {
+                write!(integer, "{}", abs_rounded.floor())?;
+            }
         }
     }
 
-    let frac_full = part.frac_pattern.join("");
-
     if part.dec_fractions
         && part.frac_max > 0
         && let Some(num) = numeric_value
     {
-        let rounded = round(num, part.frac_max);
-        let repr = rounded.to_string();
-        if let Some(idx) = repr.find('.') {
-            let frac_part = &repr[idx + 1..];
-            fraction = frac_part.to_string();
-            let mut frac_chars: Vec<char> = fraction.chars().collect();
-            let pattern_chars: Vec<char> = frac_full.chars().collect();
-            let mut pattern_idx = pattern_chars.len();
-            let mut digit_idx = frac_chars.len();
-            while pattern_idx > 0 && digit_idx > 0 {
-                pattern_idx -= 1;
-                let placeholder = pattern_chars[pattern_idx];
-                let current_digit = digit_idx - 1;
-                if (placeholder == '#' || placeholder == '?')
-                    && frac_chars.get(current_digit) == Some(&'0')
-                    && frac_chars.len() > part.frac_min
-                    && current_digit + 1 == frac_chars.len()
-                {
-                    frac_chars.pop();
-                    digit_idx -= 1;
-                    continue;
+        match &exact {
+            Some(exact) => fraction.push_str(&exact.round(part.frac_max, opts.rounding).fraction()),
+            None => {
+                let mut repr = Digits::new();
+                write!(repr, "{}", round(num, part.frac_max))?;
+                if let Some((_, digits)) = repr.as_str().split_once('.') {
+                    fraction.push_str(digits);
                 }
-                digit_idx -= 1;
             }
-            fraction = frac_chars.into_iter().collect();
         }
+        let digits = fraction.as_bytes();
+        let mut keep = digits.len();
+        let mut digit_idx = digits.len();
+        for placeholder in part.frac_p.chars().rev() {
+            if digit_idx == 0 {
+                break;
+            }
+            let current_digit = digit_idx - 1;
+            if (placeholder == '#' || placeholder == '?')
+                && digits[current_digit] == b'0'
+                && keep > part.frac_min
+                && current_digit + 1 == keep
+            {
+                keep -= 1;
+            }
+            digit_idx -= 1;
+        }
+        fraction.truncate(keep);
     }
 
     let fixed_slash =
@@ -188,131 +258,150 @@ pub fn run_part(
         if fractional != 0.0 {
             have_fraction = true;
             if let Some(den) = part.denominator {
-                denominator = den.to_string();
-                let num_val = round(fractional * den as f64, 0).round() as i64;
-                numerator = num_val.to_string();
-                if numerator == "0" {
-                    numerator.clear();
-                    denominator.clear();
+                let scaled = fractional * den as f64;
+                let num_val = match opts.rounding {
+                    RoundingMode::HalfAwayFromZero => round(scaled, 0).round() as i64,
+                    mode => {
+                        let signed = if num < 0.0 { -scaled } else { scaled };
+                        round_mode(clamp(signed), 0, mode).abs() as i64
+                    }
+                };
+                if num_val == 0 {
                     have_fraction = fixed_slash;
+                } else {
+                    write!(numerator, "{num_val}")?;
+                    write!(denominator, "{den}")?;
                 }
             } else {
                 let (num_val, den_val) = dec2frac(fractional, None, Some(part.den_max));
-                numerator = num_val.to_string();
-                denominator = den_val.to_string();
-                if part.integer && numerator == "0" {
-                    numerator.clear();
-                    denominator.clear();
+                if part.integer && num_val == 0 {
                     have_fraction = fixed_slash;
+                } else {
+                    write!(numerator, "{num_val}")?;
+                    write!(denominator, "{den_val}")?;
                 }
             }
         } else if num == 0.0 && !part.integer {
             have_fraction = true;
-            numerator = "0".to_string();
-            denominator = "1".to_string();
+            numerator.push_str("0");
+            denominator.push_str("1");
         }
         if part.integer && !have_fraction && num.trunc() == 0.0 {
-            integer = "0".to_string();
+            integer.clear();
+            integer.push_str("0");
         }
     }
 
-    let group_pri_raw = opts.grouping.first().copied().unwrap_or(3);
-    let group_sec_raw = opts.grouping.get(1).copied().unwrap_or(group_pri_raw);
-    let group_pri = group_pri_raw as usize;
-    let group_sec = group_sec_raw as usize;
+    let group_pri = opts.grouping.first().copied().unwrap_or(3) as usize;
+    let group_sec = opts
+        .grouping
+        .get(1)
+        .map_or(group_pri, |&size| size as usize);
 
     if !part.date.is_empty()
         && let Some(num) = numeric_value
     {
-        date = num.trunc();
-        let t = DAYSIZE * (num - date);
-        time = t.floor();
-        subsec = t - time;
+        let Clock {
+            date, time, subsec, ..
+        } = &mut clock;
+        *date = num.trunc();
+        let t = DAYSIZE * (num - *date);
+        *time = t.floor();
+        *subsec = t - *time;
         if subsec.abs() < 1e-6 {
-            subsec = 0.0;
-        } else if subsec > 0.9999 {
-            subsec = 0.0;
-            time += 1.0;
-            if (time - DAYSIZE).abs() < f64::EPSILON {
-                time = 0.0;
-                date += 1.0;
+            *subsec = 0.0;
+        } else if *subsec > 0.9999 {
+            *subsec = 0.0;
+            *time += 1.0;
+            if (*time - DAYSIZE).abs() < f64::EPSILON {
+                *time = 0.0;
+                *date += 1.0;
             }
         }
-        if subsec != 0.0 {
+        if *subsec != 0.0 && opts.rounding != RoundingMode::HalfAwayFromZero {
+            // Noise below a microsecond is dropped before rounding to the
+            // sub-second digits shown
+            let micros = (*subsec * 1e6).round() / 1e6;
+            let rounded = round_mode(*time + micros, part.sec_decimals as usize, opts.rounding);
+            *time = rounded.floor();
+            *subsec = rounded - *time;
+        } else if *subsec != 0.0 {
             let has_msec = part.date.contains(DateUnits::MILLISECOND);
             let has_csec = part.date.contains(DateUnits::CENTISECOND);
             let has_dsec = part.date.contains(DateUnits::DECISECOND);
             let should_round = if has_msec {
-                subsec > 0.9995
+                *subsec > 0.9995
             } else if has_csec {
-                subsec > 0.995
+                *subsec > 0.995
             } else if has_dsec {
-                subsec > 0.95
+                *subsec > 0.95
             } else {
-                subsec >= 0.5
+                *subsec >= 0.5
             };
             if should_round {
-                time += 1.0;
-                subsec = 0.0;
+                *time += 1.0;
+                *subsec = 0.0;
             }
         }
+        let (date, time) = (*date, *time);
+        // 1904 serials are converted through their 1900 equivalents
+        let offset = if opts.date_1904 {
+            EPOCH_1904_OFFSET
+        } else {
+            0.0
+        };
+        let mut date_error = None;
         if date != 0.0 || part.date_system != 0 {
-            let dt = date_from_serial(num, part.date_system, opts.leap_1900);
-            year = dt[0];
-            month = dt[1] as u8;
-            day = dt[2];
+            match date_from_serial(num + offset, part.date_system, opts.leap_1900) {
+                Ok(dt) => {
+                    clock.year = dt[0];
+                    clock.month = dt[1] as u8;
+                    clock.day = dt[2];
+                    clock.civil = [clock.year, clock.month as i32, clock.day];
+                    if part.date_system != EPOCH_1900 {
+                        clock.civil =
+                            to_ymd(num + offset, EPOCH_1900, opts.leap_1900).unwrap_or(clock.civil);
+                    }
+                }
+                Err(err) => date_error = Some(err),
+            }
         }
         if time != 0.0 {
             let x = if time < 0.0 { DAYSIZE + time } else { time };
-            second = (x as i64 % 60) as i32;
-            minute = ((x as i64 / 60) % 60) as i32;
-            hour = (((x as i64 / 60) / 60) % 60) as i32;
+            clock.second = (x as i64 % 60) as i32;
+            clock.minute = ((x as i64 / 60) % 60) as i32;
+            clock.hour = (((x as i64 / 60) / 60) % 60) as i32;
         }
-        weekday = ((6.0 + date).rem_euclid(7.0)) as usize;
+        clock.weekday = ((6.0 + date + offset).rem_euclid(7.0)) as usize;
 
         let overflow_val = date + (time / DAYSIZE);
-        if date_overflows(num, overflow_val, opts.date_span_large) {
+        if date_overflows(num, overflow_val, opts.date_span_large, offset) {
+            date_error = Some(FormatterError::DateOutOfBounds);
+        }
+        if let Some(err) = date_error {
             if opts.date_error_throws {
-                return Err(FormatterError::DateOutOfBounds);
+                return Err(err);
             }
             if opts.date_error_number {
                 let mut buffer = String::new();
                 if num < 0.0 {
                     buffer.push_str(&locale.negative);
                 }
-                format_general(&mut buffer, num, part, locale);
-                return Ok(buffer);
+                format_general(&mut buffer, num, locale)?;
+                out.role(PartKind::General);
+                out.write_str(&transliterate(buffer))?;
+            } else {
+                out.role(PartKind::Overflow);
+                out.write_str(&opts.overflow)?;
             }
-            return Ok(opts.overflow.clone());
+            return Ok(());
         }
     }
 
-    let pad_q = pad('?', opts.nbsp);
-
-    if exponent < 0 {
-        mantissa_sign = "-".to_string();
-    } else if part.exp_plus {
-        mantissa_sign = "+".to_string();
-    }
-
-    let mut output = String::new();
-    let mut counter_int = 0usize;
-    let mut counter_frac = 0usize;
-    let mut counter_man = 0usize;
-    let mut counter_num = 0usize;
-    let mut counter_den = 0usize;
-    let mut denominator_fixed = false;
-
-    let integer_chars: Vec<char> = integer.chars().collect();
-    let fraction_chars: Vec<char> = fraction.chars().collect();
-    let mantissa_chars: Vec<char> = mantissa.chars().collect();
-    let numerator_chars: Vec<char> = numerator.chars().collect();
-    let denominator_chars: Vec<char> = denominator.chars().collect();
-
     let negative_value = numeric_value.is_some_and(|n| n.is_sign_negative());
-    let has_integer_digit = integer_chars.iter().any(|c| *c != '0');
-    let has_fraction_digit = fraction_chars.iter().any(|c| *c != '0');
-    let has_numerator_digit = numerator_chars.iter().any(|c| *c != '0')
+    let has_integer_digit = integer.as_bytes().iter().any(|&c| c != b'0');
+    let has_fraction_digit = fraction.as_bytes().iter().any(|&c| c != b'0');
+    let has_numerator_digit = numerator.as_bytes().iter().any(|&c| c != b'0')
         || (part.fractions && numeric_value.is_some_and(|n| n != 0.0));
     let uses_general = part.tokens.iter().any(|tok| {
         matches!(
@@ -323,115 +412,297 @@ pub fn run_part(
     let general_has_value = uses_general && numeric_value.map(|n| n != 0.0).unwrap_or(false);
     let has_value_digits =
         has_integer_digit || has_fraction_digit || has_numerator_digit || general_has_value;
-    let show_negative_sign = negative_value && has_value_digits;
 
-    for (idx, token) in part.tokens.iter().enumerate() {
+    // the sign of a negative number in the first section, which the General
+    // budget includes as `format_general_with_width` does
+    let sign_width = if negative_value
+        && part.tokens.iter().any(|tok| {
+            matches!(
+                tok,
+                SectionToken::Token(token) if token.kind == TokenKind::Minus && token.volatile
+            )
+        }) {
+        locale.negative.width()
+    } else {
+        0
+    };
+    let mut render = Render {
+        part,
+        opts,
+        locale,
+        numeric_value,
+        text_value,
+        exact,
+        integer,
+        fraction,
+        mantissa,
+        numerator,
+        denominator,
+        mantissa_sign: if exponent < 0 {
+            "-"
+        } else if part.exp_plus {
+            "+"
+        } else {
+            ""
+        },
+        have_fraction,
+        show_negative_sign: negative_value && has_value_digits,
+        pad_q: pad('?', opts.nbsp),
+        group_pri,
+        group_sec,
+        clock,
+        counter_int: 0,
+        counter_frac: 0,
+        counter_man: 0,
+        counter_num: 0,
+        counter_den: 0,
+        denominator_fixed: false,
+        filled: false,
+        general_budget: opts.general_width.map(|max| max.saturating_sub(sign_width)),
+    };
+
+    let Some(width) = opts.width else {
+        render.tokens(out, numerals.as_ref())?;
+        return Ok(());
+    };
+
+    // Fills and overflow depend on the width of the whole section, so it is
+    // buffered and measured before being written out
+    let mut buffer = Parts::aligned();
+    render.tokens(&mut buffer, numerals.as_ref())?;
+    let mut parts = buffer.into_parts();
+    if text_value.is_none() && span(&parts) > width {
+        if let Some(general) = parts.iter().find(|p| p.kind == PartKind::General) {
+            let others = span(&parts) - general.text.width();
+            render.rewind();
+            let room = width.saturating_sub(others);
+            render.general_budget = Some(render.general_budget.map_or(room, |max| max.min(room)));
+            let mut buffer = Parts::aligned();
+            render.tokens(&mut buffer, numerals.as_ref())?;
+            parts = buffer.into_parts();
+        }
+        if span(&parts) > width {
+            out.role(PartKind::Overflow);
+            out.write_str(&opts.overflow)?;
+            return Ok(());
+        }
+    }
+    fill_to_width(out, &parts, width)?;
+
+    Ok(())
+}
+
+/// Calendar and clock fields of a date value.
+#[derive(Default)]
+struct Clock {
+    year: i32,
+    month: u8,
+    day: i32,
+    /// Gregorian date, for era lookups under other calendars
+    civil: [i32; 3],
+    weekday: usize,
+    hour: i32,
+    minute: i32,
+    second: i32,
+    subsec: f64,
+    date: f64,
+    time: f64,
+}
+
+/// Digits and flags worked out for a section, written out token by token.
+struct Render<'a> {
+    part: &'a Section,
+    opts: &'a FormatterOptions,
+    locale: &'a Locale,
+    numeric_value: Option<f64>,
+    text_value: Option<&'a str>,
+    exact: Option<Exact>,
+    integer: Digits,
+    fraction: Digits,
+    mantissa: Digits,
+    numerator: Digits,
+    denominator: Digits,
+    mantissa_sign: &'static str,
+    have_fraction: bool,
+    show_negative_sign: bool,
+    pad_q: &'static str,
+    group_pri: usize,
+    group_sec: usize,
+    clock: Clock,
+    counter_int: usize,
+    counter_frac: usize,
+    counter_man: usize,
+    counter_num: usize,
+    counter_den: usize,
+    denominator_fixed: bool,
+    /// Whether the section's fill was written; only the first one expands.
+    filled: bool,
+    /// Cells that `General` has to fit in, from the options or from the room
+    /// left when the section is too wide for its column.
+    general_budget: Option<usize>,
+}
+
+impl Render<'_> {
+    /// Resets the per-render state, to write the tokens again.
+    fn rewind(&mut self) {
+        self.counter_int = 0;
+        self.counter_frac = 0;
+        self.counter_man = 0;
+        self.counter_num = 0;
+        self.counter_den = 0;
+        self.denominator_fixed = false;
+        self.filled = false;
+    }
+
+    fn tokens<S: Sink + ?Sized>(
+        &mut self,
+        out: &mut S,
+        numerals: Option<&NumeralSystem>,
+    ) -> fmt::Result {
+        let (part, locale) = (self.part, self.locale);
+        // Tokens rewritten into other numerals are rendered on their own first
+        let mut scratch = String::new();
+        for (idx, token) in part.tokens.iter().enumerate() {
+            out.role(token_role(part, token));
+            if let Some(system) = numerals
+                && let Some(digitwise) = digitwise(token)
+            {
+                scratch.clear();
+                self.token(&mut scratch, idx, token)?;
+                out.write_str(&system.convert(
+                    &scratch,
+                    digitwise,
+                    &locale.decimal,
+                    &locale.group,
+                ))?;
+            } else {
+                self.token(out, idx, token)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn token<S: Sink + ?Sized>(
+        &mut self,
+        out: &mut S,
+        idx: usize,
+        token: &SectionToken,
+    ) -> fmt::Result {
+        let (part, opts, locale) = (self.part, self.opts, self.locale);
         match token {
             SectionToken::String(tok) => {
-                let value = match tok.rule {
+                let blank = match tok.rule {
                     Some(StringRule::Num) => {
-                        if have_fraction {
-                            tok.value.replace(' ', pad_q)
-                        } else if part.num_min > 0 || part.den_min > 0 {
-                            tok.value.chars().map(|_| pad_q).collect()
-                        } else {
-                            tok.value.replace(' ', pad_q)
-                        }
+                        !self.have_fraction && (part.num_min > 0 || part.den_min > 0)
                     }
                     Some(StringRule::NumPlusInt) => {
-                        if have_fraction && !integer.is_empty() {
-                            tok.value.replace(' ', pad_q)
-                        } else if part.den_min > 0 && (!integer.is_empty() || part.num_min > 0) {
-                            tok.value.chars().map(|_| pad_q).collect()
-                        } else {
-                            tok.value.replace(' ', pad_q)
-                        }
-                    }
-                    Some(StringRule::Den) => {
-                        if have_fraction {
-                            tok.value.replace(' ', pad_q)
-                        } else if part.den_min > 0 {
-                            tok.value.chars().map(|_| pad_q).collect()
-                        } else {
-                            tok.value.replace(' ', pad_q)
-                        }
+                        (!self.have_fraction || self.integer.is_empty())
+                            && part.den_min > 0
+                            && (!self.integer.is_empty() || part.num_min > 0)
                     }
-                    None => tok.value.replace(' ', pad_q),
+                    Some(StringRule::Den) => !self.have_fraction && part.den_min > 0,
+                    None => false,
                 };
-                output.push_str(&value);
+                for ch in tok.value.chars() {
+                    if blank || ch == ' ' {
+                        out.write_str(self.pad_q)?;
+                    } else {
+                        out.write_char(ch)?;
+                    }
+                }
             }
             SectionToken::Token(tok) => match tok.kind {
                 TokenKind::Space => {
-                    if !should_skip_fraction_space(part, have_fraction, idx) {
-                        output.push_str(pad_q);
+                    if !should_skip_fraction_space(part, self.have_fraction, idx) {
+                        out.write_str(self.pad_q)?;
                     }
                 }
-                TokenKind::Error => output.push_str(&opts.invalid),
+                TokenKind::Error => out.write_str(&opts.invalid)?,
                 TokenKind::Point => {
                     if part.date.is_empty() {
-                        output.push_str(&locale.decimal);
+                        out.write_str(&locale.decimal)?;
                     } else {
-                        output.push_str(&token_raw(tok));
+                        write_raw(out, tok)?;
                     }
                 }
                 TokenKind::General => {
-                    if let Some(num) = numeric_value {
-                        format_general(&mut output, num, part, locale);
-                    } else if let Some(text) = text_value {
-                        output.push_str(text);
+                    if let Some(max) = self.general_budget
+                        && let Some(num) = self.numeric_value
+                        && format_general_fit(out, num, max, locale)?
+                    {
+                        // narrowed to the room left in the column
+                    } else if let Some(exact) =
+                        self.exact.as_ref().filter(|e| e.fraction().is_empty())
+                    {
+                        format_general_exact(out, exact, locale)?;
+                    } else if let Some(num) = self.numeric_value {
+                        format_general(out, num, locale)?;
+                    } else if let Some(text) = self.text_value {
+                        out.write_str(text)?;
                     }
                 }
                 TokenKind::Minus => {
                     if tok.volatile && !part.date.is_empty() {
                         // no-op
-                    } else if tok.volatile && numeric_value.is_none_or(|n| n >= 0.0) {
+                    } else if tok.volatile && self.numeric_value.is_none_or(|n| n >= 0.0) {
                         // skip volatile minus for non-negative numeric values or non-numeric inputs
                     } else if tok.volatile
                         && !part.fractions
                         && (part.integer || part.dec_fractions)
                     {
-                        if show_negative_sign
-                            && ((!integer.is_empty() && integer != "0") || !fraction.is_empty())
+                        if self.show_negative_sign
+                            && ((!self.integer.is_empty() && self.integer.as_str() != "0")
+                                || !self.fraction.is_empty())
                         {
-                            output.push_str(&locale.negative);
+                            out.write_str(&locale.negative)?;
                         }
                     } else {
-                        output.push_str(&locale.negative);
+                        out.write_str(&locale.negative)?;
                     }
                 }
-                TokenKind::Plus => output.push_str(&locale.positive),
+                TokenKind::Plus => out.write_str(&locale.positive)?,
                 TokenKind::Text => {
-                    if let Some(text) = text_value {
-                        output.push_str(text);
-                    } else if let Some(num) = numeric_value {
-                        output.push_str(&num.to_string());
+                    if let Some(text) = self.text_value {
+                        out.write_str(text)?;
+                    } else if let Some(exact) = &self.exact {
+                        write!(out, "{exact}")?;
+                    } else if let Some(num) = self.numeric_value {
+                        write!(out, "{num}")?;
                     }
                 }
                 TokenKind::Fill => {
-                    if let Some(fill) = &opts.fill_char {
-                        output.push_str(fill);
-                        output.push_str(&token_raw(tok));
+                    if out.aligns() {
+                        if !self.filled {
+                            self.filled = true;
+                            out.write_str(glyph(tok))?;
+                        }
+                    } else if let Some(fill) = &opts.fill_char {
+                        out.write_str(fill)?;
+                        write_raw(out, tok)?;
                     }
                 }
                 TokenKind::Skip => {
                     if let Some(skip) = &opts.skip_char {
-                        output.push_str(skip);
-                        output.push_str(&token_raw(tok));
+                        out.write_str(skip)?;
+                        write_raw(out, tok)?;
                     } else {
-                        output.push_str(if opts.nbsp { "\u{00A0}" } else { " " });
+                        let cells = if out.aligns() { glyph(tok).width() } else { 1 };
+                        for _ in 0..cells {
+                            out.write_str(if opts.nbsp { "\u{00A0}" } else { " " })?;
+                        }
                     }
                 }
                 TokenKind::Ampm => {
-                    let idx = if hour < 12 { 0 } else { 1 };
+                    let idx = if self.clock.hour < 12 { 0 } else { 1 };
                     if tok.short && ptr::eq(locale, default_locale()) {
-                        output.push(if idx == 0 { 'A' } else { 'P' });
+                        out.write_char(if idx == 0 { 'A' } else { 'P' })?;
                     } else if let Some(val) = locale.ampm.get(idx) {
-                        output.push_str(val);
+                        out.write_str(val)?;
                     }
                 }
-                TokenKind::Percent => output.push('%'),
+                TokenKind::Percent => out.write_char('%')?,
                 TokenKind::Digit | TokenKind::Char | TokenKind::String | TokenKind::Escaped => {
-                    output.push_str(&token_raw(tok));
+                    write_raw(out, tok)?;
                 }
                 TokenKind::Locale
                 | TokenKind::Color
@@ -450,160 +721,370 @@ pub fn run_part(
                 | TokenKind::Qmark
                 | TokenKind::Slash
                 | TokenKind::Group => {}
-                _ => {
-                    output.push_str(&token_raw(tok));
-                }
+                _ => write_raw(out, tok)?,
             },
             SectionToken::Div => {
-                if have_fraction {
-                    output.push('/');
+                if self.have_fraction {
+                    out.write_char('/')?;
                 } else if part.num_min > 0
                     || part.den_min > 0
                     || part.num_p.contains('?')
                     || part.den_p.contains('?')
                 {
-                    output.push_str(pad_q);
+                    out.write_str(self.pad_q)?;
                 } else {
-                    output.push_str(pad('#', opts.nbsp));
+                    out.write_str(pad('#', opts.nbsp))?;
                 }
             }
             SectionToken::Number(NumberToken {
                 part: number_part,
                 pattern,
             }) => match number_part {
+                NumberPart::Integer if part.int_pattern.len() == 1 => {
+                    self.counter_int += self.write_integer(out)?;
+                }
                 NumberPart::Integer => {
-                    if part.int_pattern.len() == 1 {
-                        let pt_chars: Vec<char> = part.int_p.chars().collect();
-                        let pt_len = pt_chars.len();
-                        let l = usize::max(pt_len.max(part.int_min), integer_chars.len());
-                        let mut digits_str = String::new();
-
-                        for i in (1..=l).rev() {
-                            let digit = if i <= integer_chars.len() {
-                                Some(integer_chars[integer_chars.len() - i])
-                            } else {
-                                None
-                            };
-
-                            let placeholder = if digit.is_some() {
-                                None
-                            } else if i <= pt_len {
-                                Some(pt_chars[pt_len - i])
-                            } else {
-                                pt_chars.first().copied()
-                            };
-
-                            let value_piece = if let Some(ch) = digit {
-                                ch.to_string()
-                            } else {
-                                let ph = placeholder.unwrap_or('#');
-                                pad(ph, opts.nbsp).to_string()
-                            };
-
-                            let mut separator = String::new();
-                            if part.grouping
-                                && let Some(base) = i.checked_sub(1)
-                                && base >= group_pri
-                            {
-                                let n = base - group_pri;
-                                if group_sec > 0 && n.is_multiple_of(group_sec) {
-                                    if digit.is_some() || placeholder == Some('0') {
-                                        separator.push_str(&locale.group);
-                                    } else if placeholder == Some('?') {
-                                        separator.push_str(pad('?', opts.nbsp));
-                                    }
-                                }
-                            }
-
-                            digits_str.push_str(&value_piece);
-                            digits_str.push_str(&separator);
-                        }
-
-                        output.push_str(&digits_str);
-                        counter_int += l;
-                    } else {
-                        counter_int += append_digit_sequence(
-                            &mut output,
-                            &integer_chars,
-                            &part.int_p,
-                            pattern,
-                            counter_int,
-                            opts.nbsp,
-                            false,
-                        );
-                    }
+                    self.counter_int += append_digit_sequence(
+                        out,
+                        self.integer.as_bytes(),
+                        &part.int_p,
+                        pattern,
+                        self.counter_int,
+                        opts.nbsp,
+                        false,
+                    )?;
                 }
                 NumberPart::Fraction => {
-                    counter_frac += append_digit_sequence(
-                        &mut output,
-                        &fraction_chars,
-                        &frac_full,
+                    self.counter_frac += append_digit_sequence(
+                        out,
+                        self.fraction.as_bytes(),
+                        &part.frac_p,
                         pattern,
-                        counter_frac,
+                        self.counter_frac,
                         opts.nbsp,
                         true,
-                    );
+                    )?;
                 }
                 NumberPart::Mantissa => {
-                    if counter_man == 0 {
-                        output.push_str(&mantissa_sign);
+                    if self.counter_man == 0 {
+                        out.role(PartKind::ExponentSign);
+                        out.write_str(self.mantissa_sign)?;
+                        out.role(PartKind::ExponentInteger);
                     }
-                    counter_man += append_digit_sequence(
-                        &mut output,
-                        &mantissa_chars,
+                    self.counter_man += append_digit_sequence(
+                        out,
+                        self.mantissa.as_bytes(),
                         &part.man_p,
                         pattern,
-                        counter_man,
+                        self.counter_man,
                         opts.nbsp,
                         false,
-                    );
+                    )?;
                 }
                 NumberPart::Numerator => {
-                    counter_num += append_digit_sequence(
-                        &mut output,
-                        &numerator_chars,
+                    self.counter_num += append_digit_sequence(
+                        out,
+                        self.numerator.as_bytes(),
                         &part.num_p,
                         pattern,
-                        counter_num,
+                        self.counter_num,
                         opts.nbsp,
                         false,
-                    );
+                    )?;
                 }
                 NumberPart::Denominator => {
-                    counter_den += append_fraction_denominator(
-                        &mut output,
-                        &denominator_chars,
+                    self.counter_den += append_fraction_denominator(
+                        out,
+                        self.denominator.as_bytes(),
                         pattern,
-                        counter_den,
+                        self.counter_den,
                         opts.nbsp,
-                        &mut denominator_fixed,
-                    );
+                        &mut self.denominator_fixed,
+                    )?;
                 }
             },
-            SectionToken::Date(date_token) => append_date_token(
-                &mut output,
-                date_token,
-                part,
-                locale,
-                year,
-                month,
-                day,
-                weekday,
-                hour,
-                minute,
-                second,
-                subsec,
-                date,
-                time,
-                numeric_value.unwrap_or(0.0),
-            ),
-            SectionToken::Exp { .. } => {
-                output.push_str(&locale.exponent);
+            SectionToken::Date(date_token) => self.date_token(out, date_token)?,
+            SectionToken::Exp { .. } => out.write_str(&locale.exponent)?,
+        }
+        Ok(())
+    }
+
+    /// Integer digits of a single-chunk pattern, with group separators.
+    fn write_integer<S: Sink + ?Sized>(&self, out: &mut S) -> Result<usize, fmt::Error> {
+        let (part, nbsp) = (self.part, self.opts.nbsp);
+        let digits = self.integer.as_bytes();
+        let pt_len = part.int_p.chars().count();
+        let l = usize::max(pt_len.max(part.int_min), digits.len());
+
+        for i in (1..=l).rev() {
+            let digit = (i <= digits.len()).then(|| digits[digits.len() - i] as char);
+            let placeholder = if digit.is_some() {
+                None
+            } else if i <= pt_len {
+                part.int_p.chars().nth(pt_len - i)
+            } else {
+                part.int_p.chars().next()
+            };
+
+            match digit {
+                Some(ch) => out.write_char(ch)?,
+                None => out.write_str(pad(placeholder.unwrap_or('#'), nbsp))?,
+            }
+
+            if part.grouping
+                && let Some(base) = i.checked_sub(1)
+                && base >= self.group_pri
+            {
+                let n = base - self.group_pri;
+                if self.group_sec > 0 && n.is_multiple_of(self.group_sec) {
+                    out.role(PartKind::Group);
+                    if digit.is_some() || placeholder == Some('0') {
+                        out.write_str(&self.locale.group)?;
+                    } else if placeholder == Some('?') {
+                        out.write_str(pad('?', nbsp))?;
+                    }
+                    out.role(PartKind::Integer);
+                }
             }
         }
+
+        Ok(l)
     }
 
-    Ok(output)
+    fn date_token<S: Sink + ?Sized>(&self, out: &mut S, token: &DateToken) -> fmt::Result {
+        let (part, locale) = (self.part, self.locale);
+        let Clock {
+            year,
+            month,
+            day,
+            civil,
+            weekday,
+            hour,
+            minute,
+            second,
+            subsec,
+            date,
+            time,
+        } = self.clock;
+        let negative = self.numeric_value.unwrap_or(0.0) < 0.0;
+        match token.kind {
+            // era calendars count years within the era, so there is nothing to pad
+            DateTokenKind::Year | DateTokenKind::YearShort
+                if matches!(part.date_system, EPOCH_JAPANESE | EPOCH_TAIWAN) =>
+            {
+                if token.zero_pad && year < 10 {
+                    out.write_char('0')?;
+                }
+                write!(out, "{year}")?;
+            }
+            DateTokenKind::Year => {
+                write_sign(out, year < 0, &locale.negative, PartKind::Year)?;
+                write!(out, "{:04}", year.abs())?;
+            }
+            DateTokenKind::YearShort => {
+                let y = year % 100;
+                write!(out, "{:02}", y.abs())?;
+            }
+            DateTokenKind::Era => {
+                if let Some((era, _)) = locale.era(civil[0], civil[1] as u8, civil[2] as u8) {
+                    let idx = token.width.unwrap_or(1).min(era.names.len());
+                    if let Some(name) = era.names.get(idx.saturating_sub(1)) {
+                        out.write_str(name)?;
+                    }
+                }
+            }
+            DateTokenKind::EraYear => match locale.era(civil[0], civil[1] as u8, civil[2] as u8) {
+                Some((_, 1)) if part.gannen => out.write_char('元')?,
+                Some((_, era_year)) => {
+                    if token.zero_pad && era_year < 10 {
+                        out.write_char('0')?;
+                    }
+                    write!(out, "{era_year}")?;
+                }
+                None => {
+                    write_sign(out, year < 0, &locale.negative, PartKind::Year)?;
+                    write!(out, "{:04}", year.abs())?;
+                }
+            },
+            DateTokenKind::BuddhistYear => {
+                write!(out, "{}", year + 543)?;
+            }
+            DateTokenKind::BuddhistYearShort => {
+                let y = (year + 543) % 100;
+                write!(out, "{:02}", y)?;
+            }
+            DateTokenKind::Month => {
+                if token.zero_pad && month < 10 {
+                    out.write_char('0')?;
+                }
+                write!(out, "{month}")?;
+            }
+            DateTokenKind::MonthNameSingle => {
+                if let Some(ch) =
+                    month_name(part, locale, year, month, false).and_then(|s| s.chars().next())
+                {
+                    out.write_char(ch)?;
+                }
+            }
+            DateTokenKind::MonthNameShort => {
+                if let Some(name) = month_name(part, locale, year, month, true) {
+                    out.write_str(name)?;
+                }
+            }
+            DateTokenKind::MonthName => {
+                if let Some(name) = month_name(part, locale, year, month, false) {
+                    out.write_str(name)?;
+                }
+            }
+            DateTokenKind::WeekdayShort => {
+                if let Some(name) = locale.ddd.get(weekday) {
+                    out.write_str(name)?;
+                }
+            }
+            DateTokenKind::Weekday => {
+                if let Some(name) = locale.dddd.get(weekday) {
+                    out.write_str(name)?;
+                }
+            }
+            DateTokenKind::Day => {
+                if token.zero_pad && day < 10 {
+                    out.write_char('0')?;
+                }
+                write!(out, "{day}")?;
+            }
+            DateTokenKind::Hour => {
+                let mut h = hour % part.clock as i32;
+                if h == 0 && part.clock < 24 {
+                    h = part.clock as i32;
+                }
+                if token.zero_pad && h < 10 {
+                    out.write_char('0')?;
+                }
+                write!(out, "{h}")?;
+            }
+            DateTokenKind::Minute => {
+                if token.zero_pad && minute < 10 {
+                    out.write_char('0')?;
+                }
+                write!(out, "{minute}")?;
+            }
+            DateTokenKind::Second => {
+                if token.zero_pad && second < 10 {
+                    out.write_char('0')?;
+                }
+                write!(out, "{second}")?;
+            }
+            DateTokenKind::Subsecond => {
+                out.write_str(&locale.decimal)?;
+                out.role(PartKind::FractionalSecond);
+                let mut frac = Digits::new();
+                write!(frac, "{:.prec$}", subsec, prec = part.sec_decimals as usize)?;
+                if let Some(fragment) = frac.as_str().split('.').nth(1) {
+                    let len = token.decimals as usize;
+                    out.write_str(&fragment[..len.min(fragment.len())])?;
+                }
+            }
+            DateTokenKind::HourElapsed => {
+                write_sign(out, negative, &locale.negative, PartKind::Hour)?;
+                let hh = (date * 24.0) + (time / 3600.0).trunc();
+                write!(
+                    out,
+                    "{:0width$}",
+                    hh.abs() as i64,
+                    width = token.width.unwrap_or(2)
+                )?;
+            }
+            DateTokenKind::MinuteElapsed => {
+                write_sign(out, negative, &locale.negative, PartKind::Minute)?;
+                let mm = (date * 1440.0) + (time / 60.0).floor();
+                write!(
+                    out,
+                    "{:0width$}",
+                    mm.abs() as i64,
+                    width = token.width.unwrap_or(2)
+                )?;
+            }
+            DateTokenKind::SecondElapsed => {
+                write_sign(out, negative, &locale.negative, PartKind::Second)?;
+                let ss = (date * DAYSIZE) + time;
+                write!(
+                    out,
+                    "{:0width$}",
+                    ss.abs() as i64,
+                    width = token.width.unwrap_or(2)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Role of the text a token writes; tokens that mix roles switch while writing.
+fn token_role(part: &Section, token: &SectionToken) -> PartKind {
+    match token {
+        SectionToken::String(tok) => match tok.role {
+            StringRole::Literal => PartKind::Literal,
+            StringRole::Percent => PartKind::Percent,
+            StringRole::Currency => PartKind::Currency,
+        },
+        SectionToken::Token(tok) => match tok.kind {
+            TokenKind::Error => PartKind::Invalid,
+            TokenKind::Point => PartKind::Decimal,
+            TokenKind::General => PartKind::General,
+            // a minus in a date is a separator, not a sign
+            TokenKind::Minus if !part.date.is_empty() => PartKind::Literal,
+            TokenKind::Minus => PartKind::MinusSign,
+            TokenKind::Plus => PartKind::PlusSign,
+            TokenKind::Text => PartKind::Text,
+            TokenKind::Fill => PartKind::Fill,
+            TokenKind::Skip => PartKind::Skip,
+            TokenKind::Ampm => PartKind::DayPeriod,
+            TokenKind::Percent => PartKind::Percent,
+            _ => PartKind::Literal,
+        },
+        SectionToken::Div => PartKind::FractionSlash,
+        SectionToken::Number(number) => match number.part {
+            NumberPart::Integer => PartKind::Integer,
+            NumberPart::Fraction => PartKind::Fraction,
+            NumberPart::Mantissa => PartKind::ExponentInteger,
+            NumberPart::Numerator => PartKind::Numerator,
+            NumberPart::Denominator => PartKind::Denominator,
+        },
+        SectionToken::Date(date) => match date.kind {
+            DateTokenKind::Year
+            | DateTokenKind::YearShort
+            | DateTokenKind::BuddhistYear
+            | DateTokenKind::BuddhistYearShort
+            | DateTokenKind::EraYear => PartKind::Year,
+            DateTokenKind::Era => PartKind::Era,
+            DateTokenKind::Month
+            | DateTokenKind::MonthName
+            | DateTokenKind::MonthNameShort
+            | DateTokenKind::MonthNameSingle => PartKind::Month,
+            DateTokenKind::Weekday | DateTokenKind::WeekdayShort => PartKind::Weekday,
+            DateTokenKind::Day => PartKind::Day,
+            DateTokenKind::Hour | DateTokenKind::HourElapsed => PartKind::Hour,
+            DateTokenKind::Minute | DateTokenKind::MinuteElapsed => PartKind::Minute,
+            DateTokenKind::Second | DateTokenKind::SecondElapsed => PartKind::Second,
+            DateTokenKind::Subsecond => PartKind::Decimal,
+        },
+        SectionToken::Exp { .. } => PartKind::ExponentSeparator,
+    }
+}
+
+/// Writes a leading minus sign as its own part before the `kind` digits.
+fn write_sign<S: Sink + ?Sized>(
+    out: &mut S,
+    negative: bool,
+    sign: &str,
+    kind: PartKind,
+) -> fmt::Result {
+    if negative {
+        out.role(PartKind::MinusSign);
+        out.write_str(sign)?;
+        out.role(kind);
+    }
+    Ok(())
 }
 
 fn should_skip_fraction_space(part: &Section, have_fraction: bool, idx: usize) -> bool {
@@ -651,26 +1132,71 @@ fn is_fraction_component(token: &SectionToken) -> bool {
     }
 }
 
-fn token_raw(token: &Token) -> String {
+/// Character repeated by a fill or measured by a skip, without its escape.
+fn glyph(token: &Token) -> &str {
     match &token.value {
-        crate::parser::model::TokenValue::Text(text) => text.clone(),
-        crate::parser::model::TokenValue::Char(ch) => ch.to_string(),
-        _ => token.raw.clone(),
+        crate::parser::model::TokenValue::Text(text) => text.strip_prefix('\\').unwrap_or(text),
+        _ => &token.raw,
+    }
+}
+
+/// Cells taken by buffered parts, leaving out the fill that expands into the rest.
+fn span(parts: &[FormattedPart]) -> usize {
+    parts
+        .iter()
+        .filter(|part| part.kind != PartKind::Fill)
+        .map(|part| part.text.width())
+        .sum()
+}
+
+/// Replays buffered parts, repeating the fill until the output spans `width`
+/// cells. A wide fill character that cannot cover the last cell leaves a space.
+/// Output that is aligned afterwards keeps the fill as its marker.
+fn fill_to_width<S: Sink + ?Sized>(
+    out: &mut S,
+    parts: &[FormattedPart],
+    width: usize,
+) -> fmt::Result {
+    let room = width.saturating_sub(span(parts));
+    for part in parts {
+        out.role(part.kind);
+        if part.kind != PartKind::Fill || out.aligns() {
+            out.write_str(&part.text)?;
+            continue;
+        }
+        let cell = part.text.width();
+        if cell == 0 {
+            continue;
+        }
+        for _ in 0..room / cell {
+            out.write_str(&part.text)?;
+        }
+        for _ in 0..room % cell {
+            out.write_char(' ')?;
+        }
     }
+    Ok(())
 }
 
-fn append_digit_sequence(
-    output: &mut String,
-    digits: &[char],
+fn write_raw<W: Write + ?Sized>(out: &mut W, token: &Token) -> fmt::Result {
+    match &token.value {
+        crate::parser::model::TokenValue::Text(text) => out.write_str(text),
+        crate::parser::model::TokenValue::Char(ch) => out.write_char(*ch),
+        _ => out.write_str(&token.raw),
+    }
+}
+
+fn append_digit_sequence<W: Write + ?Sized>(
+    output: &mut W,
+    digits: &[u8],
     full_pattern: &str,
     chunk_pattern: &str,
     offset: usize,
     nbsp: bool,
     align_left: bool,
-) -> usize {
-    let chunk_chars: Vec<char> = chunk_pattern.chars().collect();
+) -> Result<usize, fmt::Error> {
     let full_len = full_pattern.chars().count();
-    let chunk_len = chunk_chars.len();
+    let chunk_len = chunk_pattern.chars().count();
     let digits_len = digits.len();
 
     let length = if offset == 0 && digits_len > full_len {
@@ -687,215 +1213,86 @@ fn append_digit_sequence(
     for i in 0..length {
         let idx = local_offset + i as isize;
         if idx >= 0
-            && let Some(ch) = digits.get(idx as usize)
+            && let Some(&digit) = digits.get(idx as usize)
         {
-            output.push(*ch);
+            output.write_char(digit as char)?;
             continue;
         }
-        let placeholder = chunk_chars.get(i).copied().unwrap_or('#');
-        output.push_str(pad(placeholder, nbsp));
+        let placeholder = chunk_pattern.chars().nth(i).unwrap_or('#');
+        output.write_str(pad(placeholder, nbsp))?;
     }
 
-    length
+    Ok(length)
 }
 
-fn append_fraction_denominator(
-    output: &mut String,
-    digits: &[char],
+fn append_fraction_denominator<W: Write + ?Sized>(
+    output: &mut W,
+    digits: &[u8],
     chunk_pattern: &str,
     offset: usize,
     nbsp: bool,
     denominator_fixed: &mut bool,
-) -> usize {
-    let chunk_chars: Vec<char> = chunk_pattern.chars().collect();
-    let chunk_len = chunk_chars.len();
-
-    for i in 0..chunk_len {
-        let idx = offset + i;
-        if let Some(ch) = digits.get(idx) {
-            output.push(*ch);
+) -> Result<usize, fmt::Error> {
+    let chunk_len = chunk_pattern.chars().count();
+
+    for (i, placeholder) in chunk_pattern.chars().enumerate() {
+        if let Some(&digit) = digits.get(offset + i) {
+            output.write_char(digit as char)?;
+        } else if "123456789".contains(placeholder) || (*denominator_fixed && placeholder == '0') {
+            *denominator_fixed = true;
+            output.write_char(if nbsp { '\u{00A0}' } else { ' ' })?;
+        } else if !*denominator_fixed
+            && i == chunk_len - 1
+            && placeholder == '0'
+            && digits.is_empty()
+        {
+            output.write_char('1')?;
         } else {
-            let placeholder = chunk_chars.get(i).copied().unwrap_or('#');
-            if "123456789".contains(placeholder) || (*denominator_fixed && placeholder == '0') {
-                *denominator_fixed = true;
-                output.push(if nbsp { '\u{00A0}' } else { ' ' });
-            } else if !*denominator_fixed
-                && i == chunk_len - 1
-                && placeholder == '0'
-                && digits.is_empty()
-            {
-                output.push('1');
-            } else {
-                output.push_str(pad(placeholder, nbsp));
-            }
+            output.write_str(pad(placeholder, nbsp))?;
         }
     }
 
-    chunk_len
+    Ok(chunk_len)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn append_date_token(
-    output: &mut String,
-    token: &DateToken,
+fn month_name<'a>(
     part: &Section,
-    locale: &Locale,
+    locale: &'a Locale,
     year: i32,
     month: u8,
-    day: i32,
-    weekday: usize,
-    hour: i32,
-    minute: i32,
-    second: i32,
-    subsec: f64,
-    date: f64,
-    time: f64,
-    numeric_value: f64,
-) {
-    match token.kind {
-        DateTokenKind::Year => {
-            if year < 0 {
-                output.push_str(&locale.negative);
-            }
-            output.push_str(&format!("{:04}", year.abs()));
-        }
-        DateTokenKind::YearShort => {
-            let y = year % 100;
-            output.push_str(&format!("{:02}", y.abs()));
-        }
-        DateTokenKind::Era => {}
-        DateTokenKind::BuddhistYear => {
-            output.push_str(&(year + 543).to_string());
-        }
-        DateTokenKind::BuddhistYearShort => {
-            let y = (year + 543) % 100;
-            output.push_str(&format!("{:02}", y));
-        }
-        DateTokenKind::Month => {
-            if token.zero_pad && month < 10 {
-                output.push('0');
-            }
-            output.push_str(&month.to_string());
-        }
-        DateTokenKind::MonthNameSingle => {
-            let source = if part.date_system == crate::constants::EPOCH_1317 {
-                &locale.mmmm6
-            } else {
-                &locale.mmmm
-            };
-            if let Some(ch) = source
-                .get((month as usize).saturating_sub(1))
-                .and_then(|s| s.chars().next())
-            {
-                output.push(ch);
-            }
-        }
-        DateTokenKind::MonthNameShort => {
-            let source = if part.date_system == crate::constants::EPOCH_1317 {
-                &locale.mmm6
-            } else {
-                &locale.mmm
-            };
-            if let Some(name) = source.get((month as usize).saturating_sub(1)) {
-                output.push_str(name);
-            }
-        }
-        DateTokenKind::MonthName => {
-            let source = if part.date_system == crate::constants::EPOCH_1317 {
-                &locale.mmmm6
+    short: bool,
+) -> Option<&'a str> {
+    let index = (month as usize).saturating_sub(1);
+    let name = match part.date_system {
+        EPOCH_1317 if short => locale.mmm6.get(index),
+        EPOCH_1317 => locale.mmmm6.get(index),
+        EPOCH_HEBREW => {
+            // most locales carry no Hebrew month names of their own
+            let source = if short { &locale.mmm8 } else { &locale.mmmm8 };
+            let fallback = default_locale();
+            let source = if source.is_empty() {
+                if short {
+                    &fallback.mmm8
+                } else {
+                    &fallback.mmmm8
+                }
             } else {
-                &locale.mmmm
+                source
             };
-            if let Some(name) = source.get((month as usize).saturating_sub(1)) {
-                output.push_str(name);
-            }
+            source.get(hebrew_month_index(year, month))
         }
-        DateTokenKind::WeekdayShort => {
-            if let Some(name) = locale.ddd.get(weekday) {
-                output.push_str(name);
-            }
-        }
-        DateTokenKind::Weekday => {
-            if let Some(name) = locale.dddd.get(weekday) {
-                output.push_str(name);
-            }
-        }
-        DateTokenKind::Day => {
-            if token.zero_pad && day < 10 {
-                output.push('0');
-            }
-            output.push_str(&day.to_string());
-        }
-        DateTokenKind::Hour => {
-            let mut h = hour % part.clock as i32;
-            if h == 0 && part.clock < 24 {
-                h = part.clock as i32;
-            }
-            if token.zero_pad && h < 10 {
-                output.push('0');
-            }
-            output.push_str(&h.to_string());
-        }
-        DateTokenKind::Minute => {
-            if token.zero_pad && minute < 10 {
-                output.push('0');
-            }
-            output.push_str(&minute.to_string());
-        }
-        DateTokenKind::Second => {
-            if token.zero_pad && second < 10 {
-                output.push('0');
-            }
-            output.push_str(&second.to_string());
-        }
-        DateTokenKind::Subsecond => {
-            output.push_str(&locale.decimal);
-            let frac = format!("{:.prec$}", subsec, prec = part.sec_decimals as usize);
-            if let Some(fragment) = frac.split('.').nth(1) {
-                let len = token.decimals as usize;
-                output.push_str(&fragment[..len.min(fragment.len())]);
-            }
-        }
-        DateTokenKind::HourElapsed => {
-            if numeric_value < 0.0 {
-                output.push_str(&locale.negative);
-            }
-            let hh = (date * 24.0) + (time / 3600.0).trunc();
-            output.push_str(&format!(
-                "{:0width$}",
-                hh.abs() as i64,
-                width = token.width.unwrap_or(2)
-            ));
-        }
-        DateTokenKind::MinuteElapsed => {
-            if numeric_value < 0.0 {
-                output.push_str(&locale.negative);
-            }
-            let mm = (date * 1440.0) + (time / 60.0).floor();
-            output.push_str(&format!(
-                "{:0width$}",
-                mm.abs() as i64,
-                width = token.width.unwrap_or(2)
-            ));
-        }
-        DateTokenKind::SecondElapsed => {
-            if numeric_value < 0.0 {
-                output.push_str(&locale.negative);
-            }
-            let ss = (date * DAYSIZE) + time;
-            output.push_str(&format!(
-                "{:0width$}",
-                ss.abs() as i64,
-                width = token.width.unwrap_or(2)
-            ));
-        }
-    }
+        _ if short => locale.mmm.get(index),
+        _ => locale.mmmm.get(index),
+    };
+    name.map(String::as_str)
 }
 
-fn date_overflows(value: f64, rounded: f64, big_range: bool) -> bool {
+/// `offset` moves the upper bounds (and the lower bound of the large range)
+/// so that they fall on the same calendar days under the 1904 system.
+fn date_overflows(value: f64, rounded: f64, big_range: bool, offset: f64) -> bool {
     if big_range {
-        value < MIN_L_DATE || rounded >= MAX_L_DATE
+        value < MIN_L_DATE - offset || rounded >= MAX_L_DATE - offset
     } else {
-        value < MIN_S_DATE || rounded >= MAX_S_DATE
+        value < MIN_S_DATE || rounded >= MAX_S_DATE - offset
     }
 }
@@ -1,25 +1,157 @@
-use super::{to_ymd::to_ymd, value::DateValue};
+//! Conversion between spreadsheet serial numbers and [`DateValue`].
+//!
+//! Serials count days from the epoch selected by
+//! [`FormatterOptions::date_1904`], honor [`FormatterOptions::leap_1900`] and
+//! are bounded by [`FormatterOptions::date_span_large`].
+
+use thiserror::Error;
+
+use crate::constants::{
+    EPOCH_1317, EPOCH_1900, EPOCH_1904, EPOCH_1904_OFFSET, MAX_L_DATE, MAX_S_DATE, MIN_L_DATE,
+    MIN_S_DATE,
+};
+
+use super::{
+    error::FormatterError,
+    options::FormatterOptions,
+    to_ymd::{from_hijri, to_ymd},
+    value::DateValue,
+};
 
 const DAYSIZE: f64 = 86_400.0;
 
-pub fn date_to_serial(date: &DateValue, _ignore_timezone: bool) -> Option<f64> {
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum SerialError {
+    #[error("serial {0} is outside the supported date range")]
+    OutOfRange(f64),
+    #[error("serial {0} has no date in this calendar")]
+    NoSuchDate(f64),
+    #[error("invalid date: {0:?}")]
+    InvalidDate(DateValue),
+    #[error("invalid time zone: {0}")]
+    InvalidTimeZone(String),
+}
+
+impl From<SerialError> for FormatterError {
+    fn from(value: SerialError) -> Self {
+        match value {
+            SerialError::OutOfRange(_) => Self::DateOutOfBounds,
+            SerialError::NoSuchDate(serial) => Self::InvalidDate(serial),
+            SerialError::InvalidTimeZone(name) => Self::InvalidTimeZone(name),
+            err @ SerialError::InvalidDate(_) => Self::Other(err.to_string()),
+        }
+    }
+}
+
+/// Serial of a Gregorian date, converted into the target zone when zoned.
+pub fn date_to_serial(date: &DateValue, options: &FormatterOptions) -> Result<f64, SerialError> {
+    let (year, month, day) = (date.year, date.month.unwrap_or(1), date.day.unwrap_or(1));
+    // The 1900 leap bug adds 1900-01-00 and 1900-02-29 and leaves no serial
+    // for 1899-12-30, which shares serial 0 with 1900-01-00.
+    let leap = options.leap_1900 && !options.date_1904;
+    let phantom = leap && year == 1900 && matches!((month, day), (1, 0) | (2, 29));
+    let dropped = leap && (year, month, day) == (1899, 12, 30);
+    let valid_day = (1..=days_in_month(year, month)).contains(&day) || phantom;
+    if !(1..=12).contains(&month) || !valid_day || dropped || !valid_time(date) {
+        return Err(SerialError::InvalidDate(date.clone()));
+    }
+    let serial = if phantom && month == 2 {
+        wall_serial(&date.clone().with_day(28), options)? + 1.0
+    } else {
+        wall_serial(date, options)?
+    };
+    check_range(serial, options)
+}
+
+/// Gregorian date and time of a serial, rounded to the millisecond.
+pub fn serial_to_date(serial: f64, options: &FormatterOptions) -> Result<DateValue, SerialError> {
+    let system = if options.date_1904 {
+        EPOCH_1904
+    } else {
+        EPOCH_1900
+    };
+    split_serial(serial, options, |ord| {
+        to_ymd(ord, system, options.leap_1900)
+    })
+}
+
+/// Serial of a date in the Hijri calendar used by `B2` patterns.
+pub fn hijri_to_serial(date: &DateValue, options: &FormatterOptions) -> Result<f64, SerialError> {
+    let invalid = || SerialError::InvalidDate(date.clone());
+    let ymd = [
+        date.year,
+        date.month.unwrap_or(1) as i32,
+        date.day.unwrap_or(1) as i32,
+    ];
+    if !valid_time(date) {
+        return Err(invalid());
+    }
+    let ord = from_hijri(ymd, options.leap_1900).ok_or_else(invalid)?;
+    let offset = if options.date_1904 {
+        EPOCH_1904_OFFSET
+    } else {
+        0.0
+    };
+    check_range(ord as f64 - offset + time_fraction(date), options)
+}
+
+/// Hijri date and time of a serial, as rendered by `B2` patterns.
+pub fn serial_to_hijri(serial: f64, options: &FormatterOptions) -> Result<DateValue, SerialError> {
+    let offset = if options.date_1904 {
+        EPOCH_1904_OFFSET
+    } else {
+        0.0
+    };
+    split_serial(serial, options, |ord| {
+        to_ymd(ord + offset, EPOCH_1317, options.leap_1900)
+    })
+}
+
+/// Serial of a date's wall clock, without validation or range checks. Day
+/// and month overflow roll into the following period.
+pub(crate) fn wall_serial(
+    date: &DateValue,
+    options: &FormatterOptions,
+) -> Result<f64, SerialError> {
     let month = date.month.unwrap_or(1) as u32;
     let day = date.day.unwrap_or(1) as u32;
     let year = date.year;
     let hour = date.hour.unwrap_or(0) as i64;
     let minute = date.minute.unwrap_or(0) as i64;
     let second = date.second.unwrap_or(0) as i64;
-    let millisecond = date.millisecond.unwrap_or(0) as i64;
 
     let days = days_from_civil(year, month, day);
     let seconds = hour * 3600 + minute * 60 + second;
-    let fraction = (seconds as f64 + millisecond as f64 / 1000.0) / DAYSIZE;
-    let d = days as f64 + fraction;
-    let offset = if d <= -25_509.0 { -25_568.0 } else { -25_569.0 };
-    Some(d - offset)
+    let mut d = days as f64 + time_fraction(date);
+    if let Some(zone) = &date.zone
+        && !options.ignore_timezone
+    {
+        let local = days * 86_400 + seconds;
+        let utc = local - zone.offset_for_local(local)? as i64;
+        let target = match &options.timezone {
+            Some(target) => target.offset_at(utc)?,
+            None => 0,
+        };
+        d += (utc + target as i64 - local) as f64 / DAYSIZE;
+    }
+    // Only January and February 1900 sit one day later under the leap bug.
+    let leap = options.leap_1900 && !options.date_1904;
+    let offset = if leap && (-25_568.0..-25_508.0).contains(&d) {
+        -25_568.0
+    } else {
+        -25_569.0
+    };
+    if options.date_1904 {
+        return Ok(d - offset - EPOCH_1904_OFFSET);
+    }
+    Ok(d - offset)
 }
 
-pub fn date_from_serial(serial: f64, system: i32, leap1900: bool) -> [i32; 6] {
+pub(crate) fn date_from_serial(
+    serial: f64,
+    system: i32,
+    leap1900: bool,
+) -> Result<[i32; 6], FormatterError> {
     let floor = serial.floor();
     let t = DAYSIZE * (serial - floor);
     let mut time = t.floor();
@@ -30,14 +162,81 @@ pub fn date_from_serial(serial: f64, system: i32, leap1900: bool) -> [i32; 6] {
         }
     }
 
-    let [y, m, d] = to_ymd(serial, system, leap1900);
+    let [y, m, d] = to_ymd(serial, system, leap1900)?;
     let x = if time < 0.0 { DAYSIZE + time } else { time };
     let total_seconds = x as i64;
     let hh = ((total_seconds / 60) / 60) % 60;
     let mm = (total_seconds / 60) % 60;
     let ss = total_seconds % 60;
 
-    [y, m, d, hh as i32, mm as i32, ss as i32]
+    Ok([y, m, d, hh as i32, mm as i32, ss as i32])
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn check_range(serial: f64, options: &FormatterOptions) -> Result<f64, SerialError> {
+    let offset = if options.date_1904 {
+        EPOCH_1904_OFFSET
+    } else {
+        0.0
+    };
+    let range = if options.date_span_large {
+        MIN_L_DATE - offset..MAX_L_DATE - offset
+    } else {
+        MIN_S_DATE..MAX_S_DATE - offset
+    };
+    if range.contains(&serial) {
+        Ok(serial)
+    } else {
+        Err(SerialError::OutOfRange(serial))
+    }
+}
+
+fn split_serial(
+    serial: f64,
+    options: &FormatterOptions,
+    ymd: impl Fn(f64) -> Result<[i32; 3], FormatterError>,
+) -> Result<DateValue, SerialError> {
+    check_range(serial, options)?;
+    let mut ord = serial.floor();
+    let mut ms = ((serial - ord) * DAYSIZE * 1000.0).round() as i64;
+    if ms >= 86_400_000 {
+        ord += 1.0;
+        ms = 0;
+    }
+    let [year, month, day] = ymd(ord).map_err(|_| SerialError::NoSuchDate(serial))?;
+    let seconds = ms / 1000;
+    Ok(DateValue::new(year)
+        .with_month(month as u8)
+        .with_day(day as u8)
+        .with_time(
+            (seconds / 3600) as u8,
+            (seconds / 60 % 60) as u8,
+            (seconds % 60) as u8,
+        )
+        .with_millisecond((ms % 1000) as u16))
+}
+
+fn valid_time(date: &DateValue) -> bool {
+    date.hour.unwrap_or(0) < 24
+        && date.minute.unwrap_or(0) < 60
+        && date.second.unwrap_or(0) < 60
+        && date.millisecond.unwrap_or(0) < 1000
+}
+
+fn time_fraction(date: &DateValue) -> f64 {
+    let seconds = date.hour.unwrap_or(0) as f64 * 3600.0
+        + date.minute.unwrap_or(0) as f64 * 60.0
+        + date.second.unwrap_or(0) as f64
+        + date.millisecond.unwrap_or(0) as f64 / 1000.0;
+    seconds / DAYSIZE
 }
 
 fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
@@ -1,4 +1,8 @@
-use crate::constants::{EPOCH_1317, EPOCH_1904};
+use crate::constants::{
+    EPOCH_1317, EPOCH_1904, EPOCH_HEBREW, EPOCH_JAPANESE, EPOCH_KOREAN, EPOCH_TAIWAN, EPOCH_THAI,
+};
+
+use super::{error::FormatterError, locale::builtin_locale};
 
 fn to_ymd_1900(ord: i32, leap1900: bool) -> [i32; 3] {
     if leap1900 && ord >= 0 {
@@ -31,20 +35,43 @@ fn to_ymd_1904(ord: i32) -> [i32; 3] {
     to_ymd_1900(ord + 1_462, false)
 }
 
-fn to_ymd_1317(ord: i32) -> [i32; 3] {
-    if ord == 60 {
-        panic!("#VALUE!");
-    }
-    if ord <= 1 {
-        return [1317, 8, 29];
+/// Julian day number of a serial. With the 1900 leap bug, serials before the
+/// phantom 1900-02-29 are the real dates they display as and that day itself
+/// has no equivalent in other calendars.
+fn julian_day(ord: i32, leap1900: bool) -> Result<i64, FormatterError> {
+    match ord {
+        60 if leap1900 => Err(FormatterError::InvalidDate(ord as f64)),
+        0..60 if leap1900 => Ok(ord.max(1) as i64 + 2_415_020),
+        _ => Ok(ord as i64 + 2_415_019),
     }
-    if ord < 60 {
-        return [1317, if ord < 32 { 9 } else { 10 }, 1 + ((ord - 2) % 30)];
+}
+
+/// Hijri date using the Kuwaiti algorithm.
+fn to_ymd_1317(ord: i32, leap1900: bool) -> Result<[i32; 3], FormatterError> {
+    Ok(kuwaiti(julian_day(ord, leap1900)?))
+}
+
+/// Serial of a Hijri date, inverting `kuwaiti` around the arithmetic
+/// calendar's estimate of its Julian day.
+pub(crate) fn from_hijri(ymd: [i32; 3], leap1900: bool) -> Option<i32> {
+    let [year, month, day] = ymd.map(i64::from);
+    let estimate = day
+        + (59 * (month - 1) + 1) / 2
+        + (year - 1) * 354
+        + (3 + 11 * year).div_euclid(30)
+        + 1_948_439;
+    let jdn = (estimate - 3..=estimate + 3).find(|&jdn| kuwaiti(jdn) == ymd)?;
+    let mut ord = i32::try_from(jdn - 2_415_019).ok()?;
+    if leap1900 && (2..=60).contains(&ord) {
+        ord -= 1;
     }
+    (to_ymd_1317(ord, leap1900).ok()? == ymd).then_some(ord)
+}
 
+fn kuwaiti(jdn: i64) -> [i32; 3] {
     let y = 10_631_f64 / 30.0;
     let shift1 = 8.01 / 60.0;
-    let mut z = ord as f64 + 466_935.0;
+    let mut z = (jdn - 1_948_084) as f64;
     let cyc = (z / 10_631.0).floor();
     z -= 10_631.0 * cyc;
     let j = ((z - shift1) / y).floor();
@@ -60,13 +87,110 @@ fn to_ymd_1317(ord: i32) -> [i32; 3] {
     ]
 }
 
-pub fn to_ymd(ord: f64, system: i32, leap1900: bool) -> [i32; 3] {
-    let int = ord.floor() as i32;
-    if system == EPOCH_1317 {
-        return to_ymd_1317(int);
+/// Gregorian months and days with the year counted from another epoch.
+fn to_ymd_solar(ord: i32, system: i32, leap1900: bool) -> [i32; 3] {
+    let date = to_ymd_1900(ord, leap1900);
+    let year = match system {
+        // Imperial eras come from the built-in `ja` locale.
+        EPOCH_JAPANESE => builtin_locale("ja")
+            .and_then(|ja| ja.era(date[0], date[1] as u8, date[2] as u8))
+            .map_or(date[0], |(_, year)| year),
+        EPOCH_TAIWAN => date[0] - 1911,
+        EPOCH_KOREAN => date[0] + 2333,
+        _ => date[0] + 543,
+    };
+    [year, date[1], date[2]]
+}
+
+/// R.D. (fixed day) of 1 Tishrei AM 1.
+const HEBREW_EPOCH: i64 = -1_373_427;
+
+/// Hebrew date with months counted from Tishrei; leap years insert Adar I as
+/// month 6, so Adar II is month 7.
+fn to_ymd_hebrew(ord: i32, leap1900: bool) -> Result<[i32; 3], FormatterError> {
+    let rd = julian_day(ord, leap1900)? - 1_721_425;
+    let mut year = ((rd - HEBREW_EPOCH) as f64 / 365.246_822).floor() as i64 + 1;
+    while hebrew_new_year(year + 1) <= rd {
+        year += 1;
+    }
+    while hebrew_new_year(year) > rd {
+        year -= 1;
+    }
+
+    let mut day = rd - hebrew_new_year(year);
+    let mut month = 1;
+    for length in hebrew_month_lengths(year) {
+        if day < length {
+            break;
+        }
+        day -= length;
+        month += 1;
+    }
+    Ok([year as i32, month, day as i32 + 1])
+}
+
+fn hebrew_leap_year(year: i64) -> bool {
+    (7 * year + 1).rem_euclid(19) < 7
+}
+
+fn hebrew_elapsed_days(year: i64) -> i64 {
+    let months = (235 * year - 234).div_euclid(19);
+    let parts = 12_084 + 13_753 * months;
+    let day = 29 * months + parts.div_euclid(25_920);
+    if (3 * (day + 1)).rem_euclid(7) < 3 {
+        day + 1
+    } else {
+        day
+    }
+}
+
+fn hebrew_new_year(year: i64) -> i64 {
+    let last = hebrew_elapsed_days(year - 1);
+    let this = hebrew_elapsed_days(year);
+    let next = hebrew_elapsed_days(year + 1);
+    let correction = if next - this == 356 {
+        2
+    } else if this - last == 382 {
+        1
+    } else {
+        0
+    };
+    HEBREW_EPOCH + this + correction
+}
+
+fn hebrew_month_lengths(year: i64) -> Vec<i64> {
+    let days = hebrew_new_year(year + 1) - hebrew_new_year(year);
+    let heshvan = if days % 10 == 5 { 30 } else { 29 };
+    let kislev = if days % 10 == 3 { 29 } else { 30 };
+    let mut lengths = vec![30, heshvan, kislev, 29, 30];
+    if hebrew_leap_year(year) {
+        lengths.push(30);
+    }
+    lengths.extend([29, 30, 29, 30, 29, 30, 29]);
+    lengths
+}
+
+/// Index into the 14 Hebrew month names (Tishrei … Shevat, Adar, Adar I,
+/// Adar II, Nisan … Elul) for a month as returned by `to_ymd`.
+pub fn hebrew_month_index(year: i32, month: u8) -> usize {
+    let month = month as usize;
+    match (hebrew_leap_year(year as i64), month) {
+        (_, 0..=5) => month.saturating_sub(1),
+        (false, 6) => 5,
+        (false, _) => month + 1,
+        (true, _) => month,
     }
-    if system == EPOCH_1904 {
-        return to_ymd_1904(int);
+}
+
+pub fn to_ymd(ord: f64, system: i32, leap1900: bool) -> Result<[i32; 3], FormatterError> {
+    let int = ord.floor() as i32;
+    match system {
+        EPOCH_1317 => to_ymd_1317(int, leap1900),
+        EPOCH_HEBREW => to_ymd_hebrew(int, leap1900),
+        EPOCH_1904 => Ok(to_ymd_1904(int)),
+        EPOCH_JAPANESE | EPOCH_TAIWAN | EPOCH_KOREAN | EPOCH_THAI => {
+            Ok(to_ymd_solar(int, system, leap1900))
+        }
+        _ => Ok(to_ymd_1900(int, leap1900)),
     }
-    to_ymd_1900(int, leap1900)
 }
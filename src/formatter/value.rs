@@ -2,10 +2,24 @@ use std::borrow::Cow;
 
 use num_bigint::BigInt;
 
+use super::{
+    options::FormatterOptions,
+    serial::{SerialError, serial_to_date},
+    zone::TimeZone,
+};
+
+/// Value handed to a formatter.
+///
+/// Non-exhaustive because cargo features add variants, such as `Decimal`
+/// with `rust_decimal`; matches need a wildcard arm.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum FormatValue<'a> {
     Number(f64),
     BigInt(BigInt),
+    /// Exact decimal, formatted without passing through `f64`.
+    #[cfg(feature = "rust_decimal")]
+    Decimal(rust_decimal::Decimal),
     Text(Cow<'a, str>),
     Boolean(bool),
     Null,
@@ -21,6 +35,8 @@ pub struct DateValue {
     pub minute: Option<u8>,
     pub second: Option<u8>,
     pub millisecond: Option<u16>,
+    /// Zone the wall-clock fields are expressed in; `None` means floating.
+    pub zone: Option<TimeZone>,
 }
 
 impl DateValue {
@@ -33,9 +49,15 @@ impl DateValue {
             minute: None,
             second: None,
             millisecond: None,
+            zone: None,
         }
     }
 
+    /// Gregorian date and time of an Excel serial under `options`' epoch.
+    pub fn from_serial(serial: f64, options: &FormatterOptions) -> Result<Self, SerialError> {
+        serial_to_date(serial, options)
+    }
+
     pub fn with_month(mut self, month: u8) -> Self {
         self.month = Some(month);
         self
@@ -57,6 +79,17 @@ impl DateValue {
         self.millisecond = Some(ms);
         self
     }
+
+    /// Marks the value as local time at `seconds` east of UTC.
+    pub fn with_offset(mut self, seconds: i32) -> Self {
+        self.zone = Some(TimeZone::Offset(seconds));
+        self
+    }
+
+    pub fn with_zone(mut self, zone: TimeZone) -> Self {
+        self.zone = Some(zone);
+        self
+    }
 }
 
 impl<'a> From<f64> for FormatValue<'a> {
@@ -95,6 +128,13 @@ impl<'a> From<BigInt> for FormatValue<'a> {
     }
 }
 
+#[cfg(feature = "rust_decimal")]
+impl<'a> From<rust_decimal::Decimal> for FormatValue<'a> {
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Self::Decimal(value)
+    }
+}
+
 impl<'a> From<DateValue> for FormatValue<'a> {
     fn from(value: DateValue) -> Self {
         Self::Date(value)
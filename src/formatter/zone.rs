@@ -0,0 +1,119 @@
+use super::serial::SerialError;
+
+/// Time zone attached to a [`DateValue`](super::DateValue) or targeted by
+/// [`FormatterOptions::timezone`](super::FormatterOptions::timezone).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeZone {
+    /// Fixed offset east of UTC, in seconds.
+    Offset(i32),
+    /// `UTC`, `±HH:MM`, or an IANA name such as `Europe/Berlin` (`tz` feature).
+    Named(String),
+}
+
+impl TimeZone {
+    pub const UTC: Self = Self::Offset(0);
+
+    pub fn named(name: impl Into<String>) -> Self {
+        Self::Named(name.into())
+    }
+
+    /// Offset in seconds for the instant `utc` (Unix seconds).
+    pub(crate) fn offset_at(&self, utc: i64) -> Result<i32, SerialError> {
+        match self {
+            Self::Offset(seconds) => Ok(*seconds),
+            Self::Named(name) => match fixed_offset(name) {
+                Some(seconds) => Ok(seconds),
+                None => named::offset_at(name, utc),
+            },
+        }
+    }
+
+    /// Offset in seconds for the wall-clock time `local` (Unix seconds as if
+    /// it were UTC). Skipped and repeated times resolve to the offset in
+    /// effect before the transition.
+    pub(crate) fn offset_for_local(&self, local: i64) -> Result<i32, SerialError> {
+        match self {
+            Self::Offset(seconds) => Ok(*seconds),
+            Self::Named(name) => match fixed_offset(name) {
+                Some(seconds) => Ok(seconds),
+                None => named::offset_for_local(name, local),
+            },
+        }
+    }
+}
+
+fn fixed_offset(name: &str) -> Option<i32> {
+    if ["UTC", "GMT", "Z"]
+        .iter()
+        .any(|z| name.eq_ignore_ascii_case(z))
+    {
+        return Some(0);
+    }
+    let rest = name
+        .strip_prefix("UTC")
+        .or_else(|| name.strip_prefix("GMT"))
+        .unwrap_or(name);
+    let sign = match rest.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits = rest[1..].replace(':', "");
+    if digits.is_empty() || digits.len() > 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.parse::<i32>().ok()?, 0),
+        3 => (digits[..1].parse().ok()?, digits[1..].parse().ok()?),
+        _ => (digits[..2].parse().ok()?, digits[2..].parse().ok()?),
+    };
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(feature = "tz")]
+mod named {
+    use jiff::{Timestamp, tz::TimeZone};
+
+    use super::SerialError;
+
+    fn zone(name: &str) -> Result<TimeZone, SerialError> {
+        TimeZone::get(name).map_err(|_| SerialError::InvalidTimeZone(name.to_string()))
+    }
+
+    fn out_of_range(seconds: i64) -> SerialError {
+        SerialError::OutOfRange(seconds as f64 / 86_400.0 + 25_569.0)
+    }
+
+    fn timestamp(seconds: i64) -> Result<Timestamp, SerialError> {
+        Timestamp::from_second(seconds).map_err(|_| out_of_range(seconds))
+    }
+
+    pub(super) fn offset_at(name: &str, utc: i64) -> Result<i32, SerialError> {
+        Ok(zone(name)?.to_offset(timestamp(utc)?).seconds())
+    }
+
+    pub(super) fn offset_for_local(name: &str, local: i64) -> Result<i32, SerialError> {
+        let wall = TimeZone::UTC.to_datetime(timestamp(local)?);
+        let zoned = zone(name)?
+            .to_ambiguous_zoned(wall)
+            .compatible()
+            .map_err(|_| out_of_range(local))?;
+        Ok(zoned.offset().seconds())
+    }
+}
+
+#[cfg(not(feature = "tz"))]
+mod named {
+    use super::SerialError;
+
+    pub(super) fn offset_at(name: &str, _utc: i64) -> Result<i32, SerialError> {
+        Err(SerialError::InvalidTimeZone(name.to_string()))
+    }
+
+    pub(super) fn offset_for_local(name: &str, _local: i64) -> Result<i32, SerialError> {
+        Err(SerialError::InvalidTimeZone(name.to_string()))
+    }
+}
@@ -8,8 +8,16 @@ pub mod wasm;
 #[cfg(feature = "typst-plugin")]
 pub mod typst_plugin;
 
+pub use formatter::serial::{self, SerialError};
 pub use formatter::{
-    ColorValue, DateValue, FormatValue, FormatterError, FormatterOptions, LocaleSettings,
-    add_locale, format, format_color, format_with_options,
+    CacheStats, CellLayout, ColorValue, CompiledFormat, DateValue, Era, FormatValue, Formatted,
+    FormattedPart, FormatterError, FormatterOptions, LocaleRegistry, LocaleSettings, NumfmtContext,
+    PartKind, RoundingMode, TimeZone, add_locale, clear_pattern_cache, format, format_color,
+    format_general_with_width, format_into, format_layout, format_parts, format_with_options,
+    locale_tags, pattern_cache_stats, remove_locale, set_pattern_cache_capacity,
+};
+pub use parser::{
+    DateInfo, ParsedValue, PatternInfo, PatternType, get_date_info, get_info, parse_bool,
+    parse_date, parse_format_section, parse_number, parse_pattern, parse_time, parse_value,
+    tokenize,
 };
-pub use parser::{parse_format_section, parse_pattern, tokenize};
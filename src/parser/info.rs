@@ -0,0 +1,248 @@
+use crate::constants::{CURRENCY_SYMBOLS, DateUnits};
+
+use super::error::ParseError;
+use super::model::{DateTokenKind, Pattern, Section, SectionToken, StringRole};
+use super::pattern::parse_pattern;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternType {
+    General,
+    Number,
+    Currency,
+    Date,
+    Time,
+    DateTime,
+    Percent,
+    Fraction,
+    Scientific,
+    Text,
+}
+
+/// Summary of a pattern's positive section, modelled after numfmt's `getInfo`.
+///
+/// `code` follows the Excel `CELL("format")` convention: `G`, `F2`, `,2`,
+/// `C2`, `P0`, `S2`, `D1`-`D9` or `@`, suffixed with `-` when negative values
+/// are colored and `()` when positive values use parentheses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternInfo {
+    pub kind: PatternType,
+    pub decimals: usize,
+    pub grouped: bool,
+    pub parentheses: bool,
+    pub color: bool,
+    pub code: String,
+}
+
+/// Date and time parts shown by any of a pattern's numeric sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateInfo {
+    pub year: bool,
+    pub month: bool,
+    pub day: bool,
+    pub hours: bool,
+    pub minutes: bool,
+    pub seconds: bool,
+    pub clock: u8,
+    /// Units rendered as elapsed time (`[h]`, `[mm]`, `[ss]`).
+    pub elapsed: DateUnits,
+}
+
+impl Pattern {
+    pub fn info(&self) -> PatternInfo {
+        let positive = &self.partitions[0];
+        let kind = self.pattern_type();
+        let decimals = match kind {
+            PatternType::Date | PatternType::Time | PatternType::DateTime => {
+                positive.sec_decimals as usize
+            }
+            PatternType::General | PatternType::Text | PatternType::Fraction => 0,
+            _ => positive.frac_max,
+        };
+        let grouped = positive.grouping;
+        let parentheses = positive.parens;
+        let color = self
+            .partitions
+            .get(1)
+            .is_some_and(|part| part.color.is_some());
+
+        let mut code = match kind {
+            PatternType::Text => "@".to_string(),
+            PatternType::General | PatternType::Fraction => "G".to_string(),
+            PatternType::Date | PatternType::Time | PatternType::DateTime => {
+                date_code(positive).to_string()
+            }
+            PatternType::Scientific => format!("S{decimals}"),
+            PatternType::Percent => format!("P{decimals}"),
+            PatternType::Currency => format!("C{decimals}"),
+            PatternType::Number if grouped => format!(",{decimals}"),
+            PatternType::Number => format!("F{decimals}"),
+        };
+        if color {
+            code.push('-');
+        }
+        if parentheses {
+            code.push_str("()");
+        }
+
+        PatternInfo {
+            kind,
+            decimals,
+            grouped,
+            parentheses,
+            color,
+            code,
+        }
+    }
+
+    pub fn date_info(&self) -> DateInfo {
+        let mut units = DateUnits::empty();
+        let mut elapsed = DateUnits::empty();
+        let mut clock = 24;
+        for section in self.partitions.iter().take(3) {
+            units |= section.date;
+            if section.clock == 12 && !section.date.is_empty() {
+                clock = 12;
+            }
+            for token in &section.tokens {
+                if let SectionToken::Date(date) = token
+                    && matches!(
+                        date.kind,
+                        DateTokenKind::HourElapsed
+                            | DateTokenKind::MinuteElapsed
+                            | DateTokenKind::SecondElapsed
+                    )
+                {
+                    elapsed |= date.unit;
+                }
+            }
+        }
+
+        DateInfo {
+            year: units.contains(DateUnits::YEAR),
+            month: units.contains(DateUnits::MONTH),
+            day: units.contains(DateUnits::DAY),
+            hours: units.contains(DateUnits::HOUR),
+            minutes: units.contains(DateUnits::MINUTE),
+            seconds: units.intersects(
+                DateUnits::SECOND
+                    | DateUnits::DECISECOND
+                    | DateUnits::CENTISECOND
+                    | DateUnits::MILLISECOND,
+            ),
+            clock,
+            elapsed,
+        }
+    }
+
+    pub fn is_date(&self) -> bool {
+        !self.partitions[0].date.is_empty()
+    }
+
+    pub fn is_percent(&self) -> bool {
+        self.partitions[0].percent
+    }
+
+    /// True when the pattern only defines a text section, such as `@`.
+    pub fn is_text(&self) -> bool {
+        let positive = &self.partitions[0];
+        positive.generated
+            && positive.general
+            && self
+                .partitions
+                .get(3)
+                .is_some_and(|part| part.text && !part.generated)
+    }
+
+    fn pattern_type(&self) -> PatternType {
+        let positive = &self.partitions[0];
+        if self.is_text() {
+            return PatternType::Text;
+        }
+        if !positive.date.is_empty() {
+            let has_date = positive
+                .date
+                .intersects(DateUnits::YEAR | DateUnits::MONTH | DateUnits::DAY);
+            let has_time = positive.date.intersects(
+                DateUnits::HOUR
+                    | DateUnits::MINUTE
+                    | DateUnits::SECOND
+                    | DateUnits::DECISECOND
+                    | DateUnits::CENTISECOND
+                    | DateUnits::MILLISECOND,
+            );
+            return match (has_date, has_time) {
+                (true, true) => PatternType::DateTime,
+                (false, true) => PatternType::Time,
+                _ => PatternType::Date,
+            };
+        }
+        if positive.general {
+            PatternType::General
+        } else if positive.exponential {
+            PatternType::Scientific
+        } else if positive.percent {
+            PatternType::Percent
+        } else if positive.fractions {
+            PatternType::Fraction
+        } else if has_currency(positive) {
+            PatternType::Currency
+        } else if positive.int_pattern.is_empty() && positive.frac_pattern.is_empty() {
+            PatternType::General
+        } else {
+            PatternType::Number
+        }
+    }
+}
+
+pub fn get_info(pattern: &str) -> Result<PatternInfo, ParseError> {
+    parse_pattern(pattern).map(|parsed| parsed.info())
+}
+
+pub fn get_date_info(pattern: &str) -> Result<DateInfo, ParseError> {
+    parse_pattern(pattern).map(|parsed| parsed.date_info())
+}
+
+fn has_currency(section: &Section) -> bool {
+    section.tokens.iter().any(|token| {
+        matches!(
+            token,
+            SectionToken::String(s) if s.role == StringRole::Currency
+                || CURRENCY_SYMBOLS.iter().any(|sym| s.value.contains(sym))
+        )
+    })
+}
+
+fn date_code(section: &Section) -> &'static str {
+    let units = section.date;
+    let named_month = section.tokens.iter().any(|token| {
+        matches!(
+            token,
+            SectionToken::Date(date) if matches!(
+                date.kind,
+                DateTokenKind::MonthName
+                    | DateTokenKind::MonthNameShort
+                    | DateTokenKind::MonthNameSingle
+            )
+        )
+    });
+
+    if units.intersects(DateUnits::YEAR | DateUnits::MONTH | DateUnits::DAY) {
+        let day = units.contains(DateUnits::DAY);
+        let year = units.contains(DateUnits::YEAR);
+        return match (named_month, day, year) {
+            (true, true, true) => "D1",
+            (true, true, false) => "D2",
+            (true, false, true) => "D3",
+            (false, true, false) => "D5",
+            _ => "D4",
+        };
+    }
+
+    let seconds = units.contains(DateUnits::SECOND);
+    match (section.clock == 12, seconds) {
+        (true, true) => "D6",
+        (true, false) => "D7",
+        (false, true) => "D8",
+        (false, false) => "D9",
+    }
+}
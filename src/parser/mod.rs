@@ -1,14 +1,19 @@
 pub mod error;
 pub mod model;
 
+mod info;
 mod pattern;
 mod section;
 mod tokenizer;
+mod value;
 
+pub use info::{DateInfo, PatternInfo, PatternType, get_date_info, get_info};
 pub use model::{
     Color, Condition, ConditionOperator, DateToken, DateTokenKind, NumberPart, NumberToken,
-    Pattern, Section, SectionToken, StringRule, StringToken, Token, TokenKind, TokenValue,
+    Numerals, Pattern, Section, SectionToken, StringRole, StringRule, StringToken, Token,
+    TokenKind, TokenValue,
 };
 pub use pattern::parse_pattern;
 pub use section::{SectionParseResult, parse_format_section};
 pub use tokenizer::tokenize;
+pub use value::{ParsedValue, parse_bool, parse_date, parse_number, parse_time, parse_value};
@@ -114,6 +114,15 @@ pub enum Color {
     Index(u32),
 }
 
+/// Numeral substitution requested by a `[DBNum]`/`[NatNum]` modifier or by
+/// the numeral byte of a `[$-D000000]` locale code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Numerals {
+    DbNum(u8),
+    NatNum(u8),
+    Script(u8),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NumberPart {
     Integer,
@@ -145,10 +154,20 @@ pub enum StringRule {
     Den,
 }
 
+/// What a literal stands for in the output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StringRole {
+    #[default]
+    Literal,
+    Percent,
+    Currency,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct StringToken {
     pub value: String,
     pub rule: Option<StringRule>,
+    pub role: StringRole,
 }
 
 impl StringToken {
@@ -156,8 +175,14 @@ impl StringToken {
         Self {
             value: value.into(),
             rule: None,
+            role: StringRole::Literal,
         }
     }
+
+    pub fn with_role(mut self, role: StringRole) -> Self {
+        self.role = role;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -167,6 +192,7 @@ pub enum DateTokenKind {
     BuddhistYear,
     BuddhistYearShort,
     Era,
+    EraYear,
     Month,
     MonthName,
     MonthNameShort,
@@ -261,12 +287,15 @@ pub struct Section {
     pub den_min: usize,
     pub den_max: usize,
     pub int_p: String,
+    pub frac_p: String,
     pub man_p: String,
     pub num_p: String,
     pub den_p: String,
     pub condition: Option<Condition>,
     pub color: Option<Color>,
     pub locale: Option<String>,
+    pub numerals: Option<Numerals>,
+    pub gannen: bool,
     pub parens: bool,
     pub generated: bool,
     pub pattern: String,
@@ -310,12 +339,15 @@ impl Section {
             den_min: 0,
             den_max: 0,
             int_p: String::new(),
+            frac_p: String::new(),
             man_p: String::new(),
             num_p: String::new(),
             den_p: String::new(),
             condition: None,
             color: None,
             locale: None,
+            numerals: None,
+            gannen: false,
             parens: false,
             generated: false,
             pattern: String::new(),
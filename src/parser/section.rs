@@ -1,11 +1,14 @@
 use std::cmp::max;
 
-use crate::constants::{DateUnits, EPOCH_1317};
+use crate::constants::{
+    CURRENCY_SYMBOLS, DateUnits, EPOCH_1317, EPOCH_1900, EPOCH_HEBREW, EPOCH_JAPANESE,
+    EPOCH_KOREAN, EPOCH_TAIWAN, EPOCH_THAI,
+};
 
 use super::error::ParseError;
 use super::model::{
-    Color, DateToken, DateTokenKind, NumberPart, NumberToken, Section, SectionToken, StringRule,
-    StringToken, Token, TokenKind, TokenValue,
+    Color, DateToken, DateTokenKind, NumberPart, NumberToken, Numerals, Section, SectionToken,
+    StringRole, StringRule, StringToken, Token, TokenKind, TokenValue,
 };
 
 pub struct SectionParseResult {
@@ -188,7 +191,7 @@ pub fn parse_format_section(input_tokens: &[Token]) -> Result<SectionParseResult
                     if value.eq_ignore_ascii_case("B2") {
                         section.date_system = EPOCH_1317;
                     } else {
-                        section.date_system = crate::constants::EPOCH_1900;
+                        section.date_system = EPOCH_1900;
                     }
                 }
             }
@@ -206,7 +209,13 @@ pub fn parse_format_section(input_tokens: &[Token]) -> Result<SectionParseResult
                 tokens.push(SectionToken::Token(tok));
             }
             TokenKind::Escaped | TokenKind::String | TokenKind::Char => {
-                push_string(&mut tokens, token_display(token));
+                let value = token_display(token);
+                let role = if CURRENCY_SYMBOLS.contains(&value.as_str()) {
+                    StringRole::Currency
+                } else {
+                    StringRole::Literal
+                };
+                push_role(&mut tokens, value, role);
             }
             TokenKind::Condition => {
                 if let TokenValue::Condition(cond) = &token.value {
@@ -225,7 +234,7 @@ pub fn parse_format_section(input_tokens: &[Token]) -> Result<SectionParseResult
             TokenKind::Percent => {
                 section.scale = 100.0;
                 section.percent = true;
-                push_string(&mut tokens, "%");
+                push_role(&mut tokens, "%", StringRole::Percent);
             }
             TokenKind::Exp => {
                 section.exponential = true;
@@ -238,7 +247,12 @@ pub fn parse_format_section(input_tokens: &[Token]) -> Result<SectionParseResult
                 tokens.push(SectionToken::Token(token.clone()));
             }
             TokenKind::DbNum | TokenKind::NatNum => {
-                // unsupported but tolerated
+                // unknown variants are tolerated and render as plain digits
+                if let Some(numerals) =
+                    token_text(token).and_then(|text| numerals_of(token.kind, text))
+                {
+                    section.numerals = Some(numerals);
+                }
             }
             TokenKind::Error => {
                 return Err(ParseError::new(format!(
@@ -309,6 +323,15 @@ fn token_text(token: &Token) -> Option<&str> {
     }
 }
 
+fn numerals_of(kind: TokenKind, text: &str) -> Option<Numerals> {
+    let (prefix, make): (usize, fn(u8) -> Numerals) = match kind {
+        TokenKind::DbNum => ("dbnum".len(), Numerals::DbNum),
+        TokenKind::NatNum => ("natnum".len(), Numerals::NatNum),
+        _ => return None,
+    };
+    text.get(prefix..)?.trim().parse().ok().map(make)
+}
+
 fn token_value_char(token: &Token) -> Option<char> {
     match &token.value {
         TokenValue::Text(text) => text.chars().next(),
@@ -327,8 +350,13 @@ fn token_display(token: &Token) -> String {
 }
 
 fn push_string(tokens: &mut Vec<SectionToken>, value: impl Into<String>) {
-    let value = value.into();
-    tokens.push(SectionToken::String(StringToken::new(value)));
+    push_role(tokens, value, StringRole::Literal);
+}
+
+fn push_role(tokens: &mut Vec<SectionToken>, value: impl Into<String>, role: StringRole) {
+    tokens.push(SectionToken::String(
+        StringToken::new(value).with_role(role),
+    ));
 }
 
 fn handle_duration_token(
@@ -376,14 +404,16 @@ fn handle_datetime_token(
         'y' => {
             if value.len() <= 2 {
                 dt.kind = DateTokenKind::YearShort;
+                dt.zero_pad = value.len() == 2;
             } else {
                 dt.kind = DateTokenKind::Year;
             }
             dt.unit = DateUnits::YEAR;
         }
         'e' => {
-            dt.kind = DateTokenKind::Year;
+            dt.kind = DateTokenKind::EraYear;
             dt.unit = DateUnits::YEAR;
+            dt.zero_pad = value.len() >= 2;
         }
         'b' => {
             dt.unit = DateUnits::YEAR;
@@ -407,6 +437,7 @@ fn handle_datetime_token(
         'g' => {
             dt.unit = DateUnits::empty();
             dt.kind = DateTokenKind::Era;
+            dt.width = Some(value.len());
         }
         'h' => {
             dt.unit = DateUnits::HOUR;
@@ -500,23 +531,45 @@ fn handle_locale_token(token: &Token, section: &mut Section, tokens: &mut Vec<Se
         if let Some(currency) = parts.next()
             && !currency.is_empty()
         {
-            push_string(tokens, currency);
+            push_role(tokens, currency, StringRole::Currency);
+        }
+        let mut code: String = parts.collect::<Vec<_>>().join("-");
+        // the first year of a Japanese era is written 元 ("gannen")
+        let lower = code.to_ascii_lowercase();
+        if let Some(pos) = lower.find("-x-gannen") {
+            section.gannen = true;
+            code.replace_range(pos..pos + "-x-gannen".len(), "");
         }
-        let code: String = parts.collect::<Vec<_>>().join("-");
         if !code.is_empty() {
             section.locale = Some(code.clone());
-            if let Ok(wincode) = i32::from_str_radix(&code, 16)
-                && (wincode & 0xff0000) != 0
-            {
-                let cal = (wincode >> 16) & 0xff;
-                if cal == 6 {
-                    section.date_system = EPOCH_1317;
+            if let Ok(wincode) = u32::from_str_radix(&code, 16) {
+                if let Some(system) = calendar_system((wincode >> 16) & 0xff) {
+                    section.date_system = system;
+                }
+                let digits = (wincode >> 24) & 0xff;
+                if digits != 0 {
+                    section.numerals = Some(Numerals::Script(digits as u8));
                 }
             }
         }
     }
 }
 
+/// Maps the calendar byte of a locale code to a date system.
+fn calendar_system(calendar: u32) -> Option<i32> {
+    match calendar {
+        // Gregorian, localized or in one of its transliterated variants
+        1 | 2 | 9..=12 => Some(EPOCH_1900),
+        3 => Some(EPOCH_JAPANESE),
+        4 => Some(EPOCH_TAIWAN),
+        5 => Some(EPOCH_KOREAN),
+        6 => Some(EPOCH_1317),
+        7 => Some(EPOCH_THAI),
+        8 => Some(EPOCH_HEBREW),
+        _ => None,
+    }
+}
+
 fn parse_color(token: &Token) -> Option<Color> {
     let value = token_text(token)?.to_ascii_lowercase();
     if let Some(rest) = value.strip_prefix("color") {
@@ -652,6 +705,7 @@ fn compute_number_padding(section: &mut Section) {
     section.den_min = den_min;
 
     section.int_p = int_pattern;
+    section.frac_p = frac_pattern;
     section.man_p = man_pattern;
     section.num_p = num_pat;
     section.den_p = den_pat;
@@ -0,0 +1,575 @@
+use std::sync::Arc;
+
+use crate::constants::CURRENCY_SYMBOLS;
+use crate::formatter::locale::{self, Locale};
+use crate::formatter::serial::date_to_serial;
+use crate::formatter::{DateValue, FormatValue, FormatterOptions};
+
+const DAYSIZE: f64 = 86_400.0;
+
+/// A value recognized from user input together with a format pattern that
+/// reproduces the way it was typed, when one is needed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedValue {
+    pub value: FormatValue<'static>,
+    pub format: Option<String>,
+}
+
+impl ParsedValue {
+    fn number(value: f64, format: Option<String>) -> Self {
+        Self {
+            value: FormatValue::Number(value),
+            format,
+        }
+    }
+}
+
+/// Parses a number, date, time or boolean, in that order of preference.
+pub fn parse_value(input: &str, options: &FormatterOptions) -> Option<ParsedValue> {
+    parse_number(input, options)
+        .or_else(|| parse_date(input, options))
+        .or_else(|| parse_time(input, options))
+        .or_else(|| parse_bool(input, options))
+}
+
+pub fn parse_number(input: &str, options: &FormatterOptions) -> Option<ParsedValue> {
+    let locale = &options_locale(options);
+    let mut s = input.trim();
+    let mut negative = false;
+    let mut signed = false;
+
+    let parens = if let Some(inner) = s.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        negative = true;
+        s = inner.trim();
+        true
+    } else {
+        false
+    };
+
+    if !parens && let Some((neg, rest)) = take_sign(s, locale) {
+        negative = neg;
+        signed = true;
+        s = rest.trim_start();
+    }
+
+    let mut currency: Option<(&str, bool, bool)> = None;
+    if let Some((symbol, rest)) = take_currency_prefix(s) {
+        let spaced = rest.starts_with(char::is_whitespace);
+        currency = Some((symbol, true, spaced));
+        s = rest.trim_start();
+        if !signed
+            && !parens
+            && let Some((neg, rest)) = take_sign(s, locale)
+        {
+            negative = neg;
+            s = rest.trim_start();
+        }
+    }
+
+    let mut percent = false;
+    if let Some(rest) = s
+        .strip_suffix(locale.percent.as_str())
+        .or_else(|| s.strip_suffix('%'))
+    {
+        percent = true;
+        s = rest.trim_end();
+    }
+
+    if currency.is_none()
+        && let Some((symbol, rest)) = take_currency_suffix(s)
+    {
+        let spaced = rest.ends_with(char::is_whitespace);
+        currency = Some((symbol, false, spaced));
+        s = rest.trim_end();
+    }
+
+    if percent && currency.is_some() {
+        return None;
+    }
+
+    let body = parse_number_body(s, locale)?;
+    let mut value = body.value;
+    if percent {
+        value /= 100.0;
+    }
+    if negative {
+        value = -value;
+    }
+
+    let frac = if body.decimals > 0 { ".00" } else { "" };
+    let mut format = if body.exponent {
+        Some("0.00E+00".to_string())
+    } else if percent {
+        Some(format!("0{frac}%"))
+    } else if let Some((symbol, prefix, spaced)) = currency {
+        let space = if spaced { " " } else { "" };
+        Some(if prefix {
+            format!("{symbol}{space}#,##0{frac}")
+        } else {
+            format!("#,##0{frac}{space}{symbol}")
+        })
+    } else if body.grouped {
+        Some(format!("#,##0{frac}"))
+    } else {
+        None
+    };
+
+    if parens {
+        let base = format.unwrap_or_else(|| format!("0{frac}"));
+        format = Some(format!("{base}_);({base})"));
+    }
+
+    Some(ParsedValue::number(value, format))
+}
+
+pub fn parse_bool(input: &str, options: &FormatterOptions) -> Option<ParsedValue> {
+    let locale = &options_locale(options);
+    let s = input.trim().to_lowercase();
+    let value = if s == "true" || s == locale.bool_true().to_lowercase() {
+        true
+    } else if s == "false" || s == locale.bool_false().to_lowercase() {
+        false
+    } else {
+        return None;
+    };
+    Some(ParsedValue {
+        value: FormatValue::Boolean(value),
+        format: None,
+    })
+}
+
+/// Parses a calendar date, optionally followed by a time of day.
+pub fn parse_date(input: &str, options: &FormatterOptions) -> Option<ParsedValue> {
+    let locale = &options_locale(options);
+    let s = input.trim();
+
+    if let Some(date) = match_date(s, locale, options) {
+        return Some(ParsedValue::number(date.serial, Some(date.format)));
+    }
+
+    for (idx, ch) in s.char_indices() {
+        if !(ch.is_whitespace() || ch == 'T') {
+            continue;
+        }
+        let head = s[..idx].trim_end();
+        let tail = s[idx + ch.len_utf8()..].trim_start();
+        if let Some(date) = match_date(head, locale, options)
+            && let Some(time) = match_time(tail, locale)
+            && !time.elapsed
+        {
+            let format = format!("{} {}", date.format, time.format);
+            return Some(ParsedValue::number(
+                date.serial + time.fraction,
+                Some(format),
+            ));
+        }
+    }
+
+    None
+}
+
+pub fn parse_time(input: &str, options: &FormatterOptions) -> Option<ParsedValue> {
+    let locale = &options_locale(options);
+    let time = match_time(input.trim(), locale)?;
+    Some(ParsedValue::number(time.fraction, Some(time.format)))
+}
+
+fn options_locale(options: &FormatterOptions) -> Arc<Locale> {
+    let tag = (!options.locale.is_empty()).then_some(options.locale.as_str());
+    locale::options_locale(tag, options)
+}
+
+fn take_sign<'a>(s: &'a str, locale: &Locale) -> Option<(bool, &'a str)> {
+    if let Some(rest) = s
+        .strip_prefix(locale.negative.as_str())
+        .or_else(|| s.strip_prefix('-'))
+        .or_else(|| s.strip_prefix('\u{2212}'))
+    {
+        return Some((true, rest));
+    }
+    s.strip_prefix(locale.positive.as_str())
+        .or_else(|| s.strip_prefix('+'))
+        .map(|rest| (false, rest))
+}
+
+fn take_currency_prefix(s: &str) -> Option<(&'static str, &str)> {
+    CURRENCY_SYMBOLS
+        .iter()
+        .find_map(|sym| s.strip_prefix(sym).map(|rest| (*sym, rest)))
+}
+
+fn take_currency_suffix(s: &str) -> Option<(&'static str, &str)> {
+    CURRENCY_SYMBOLS
+        .iter()
+        .find_map(|sym| s.strip_suffix(sym).map(|rest| (*sym, rest)))
+}
+
+struct NumberBody {
+    value: f64,
+    decimals: usize,
+    grouped: bool,
+    exponent: bool,
+}
+
+fn parse_number_body(s: &str, locale: &Locale) -> Option<NumberBody> {
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(idx) => {
+            let exp = &s[idx + 1..];
+            let digits = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+            if !is_digits(digits) {
+                return None;
+            }
+            (&s[..idx], Some(exp))
+        }
+        None => (s, None),
+    };
+
+    let (int_part, frac_part) = match mantissa.split_once(locale.decimal.as_str()) {
+        Some((int, frac)) => (int, frac),
+        None => (mantissa, ""),
+    };
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None;
+    }
+    if !frac_part.is_empty() && !is_digits(frac_part) {
+        return None;
+    }
+
+    let mut grouped = false;
+    let mut int_digits = String::new();
+    if !int_part.is_empty() && !is_digits(int_part) {
+        let mut groups = int_part.split(|c: char| is_group_char(c, locale));
+        let first = groups.next()?;
+        if first.is_empty() || first.len() > 3 || !is_digits(first) {
+            return None;
+        }
+        int_digits.push_str(first);
+        for group in groups {
+            if group.len() != 3 || !is_digits(group) {
+                return None;
+            }
+            int_digits.push_str(group);
+        }
+        grouped = true;
+    } else {
+        int_digits.push_str(int_part);
+    }
+
+    let mut normalized = if int_digits.is_empty() {
+        "0".to_string()
+    } else {
+        int_digits
+    };
+    if !frac_part.is_empty() {
+        normalized.push('.');
+        normalized.push_str(frac_part);
+    }
+    if let Some(exp) = exponent {
+        normalized.push('e');
+        normalized.push_str(exp);
+    }
+
+    Some(NumberBody {
+        value: normalized.parse::<f64>().ok().filter(|v| v.is_finite())?,
+        decimals: frac_part.len(),
+        grouped,
+        exponent: exponent.is_some(),
+    })
+}
+
+fn is_group_char(ch: char, locale: &Locale) -> bool {
+    let mut buf = [0u8; 4];
+    if locale.group == *ch.encode_utf8(&mut buf) {
+        return true;
+    }
+    let spacey = |c: char| matches!(c, ' ' | '\u{00A0}' | '\u{202F}');
+    locale.group.chars().all(spacey) && spacey(ch)
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Piece<'a> {
+    Num(&'a str),
+    Word(&'a str),
+    Sep(&'a str),
+}
+
+fn split_pieces(s: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut chars = s.char_indices().peekable();
+    while let Some((idx, ch)) = chars.next() {
+        let class = char_class(ch);
+        let mut end = idx + ch.len_utf8();
+        while let Some(&(next_idx, next)) = chars.peek() {
+            if char_class(next) != class {
+                break;
+            }
+            end = next_idx + next.len_utf8();
+            chars.next();
+        }
+        let text = &s[start..end];
+        pieces.push(match class {
+            0 => Piece::Num(text),
+            1 => Piece::Word(text),
+            _ => Piece::Sep(text),
+        });
+        start = end;
+    }
+    pieces
+}
+
+fn char_class(ch: char) -> u8 {
+    if ch.is_ascii_digit() {
+        0
+    } else if ch.is_alphabetic() {
+        1
+    } else {
+        2
+    }
+}
+
+struct DateMatch {
+    serial: f64,
+    format: String,
+}
+
+fn match_date(s: &str, locale: &Locale, options: &FormatterOptions) -> Option<DateMatch> {
+    let pieces = split_pieces(s);
+    let mut values = Vec::new();
+    let mut seps: Vec<&str> = Vec::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        match piece {
+            Piece::Sep(sep) => {
+                if i == 0 {
+                    return None;
+                }
+                if i == pieces.len() - 1 {
+                    if sep.trim() != "." {
+                        return None;
+                    }
+                    continue;
+                }
+                seps.push(sep);
+            }
+            _ => {
+                if values.len() > seps.len() {
+                    return None;
+                }
+                values.push(*piece);
+            }
+        }
+    }
+
+    let (year, month, day, format) = match values.as_slice() {
+        [Piece::Num(a), Piece::Num(b), Piece::Num(c)] => {
+            let sep = seps[0].trim();
+            if seps[0] != seps[1] || !matches!(sep, "/" | "-" | ".") {
+                return None;
+            }
+            if a.len() == 4 {
+                let format = join_format(&[year_code(a), month_code(b), day_code(c)], &seps);
+                (a.parse().ok()?, b.parse().ok()?, c.parse().ok()?, format)
+            } else if locale.prefer_mdy {
+                let format = join_format(&[month_code(a), day_code(b), year_code(c)], &seps);
+                (parse_year(c)?, a.parse().ok()?, b.parse().ok()?, format)
+            } else {
+                let format = join_format(&[day_code(a), month_code(b), year_code(c)], &seps);
+                (parse_year(c)?, b.parse().ok()?, a.parse().ok()?, format)
+            }
+        }
+        [Piece::Num(d), Piece::Word(m), Piece::Num(y)] => {
+            let (month, code) = match_month_name(m, locale)?;
+            let format = join_format(&[day_code(d), code, year_code(y)], &seps);
+            (parse_year(y)?, month, d.parse().ok()?, format)
+        }
+        [Piece::Word(m), Piece::Num(d), Piece::Num(y)] => {
+            let (month, code) = match_month_name(m, locale)?;
+            let format = join_format(&[code, day_code(d), year_code(y)], &seps);
+            (parse_year(y)?, month, d.parse().ok()?, format)
+        }
+        [Piece::Word(m), Piece::Num(y)] if y.len() == 4 => {
+            let (month, code) = match_month_name(m, locale)?;
+            let format = join_format(&[code, year_code(y)], &seps);
+            (y.parse().ok()?, month, 1, format)
+        }
+        _ => return None,
+    };
+
+    let serial = date_serial(year, month, day, options)?;
+    Some(DateMatch { serial, format })
+}
+
+fn join_format(codes: &[&str], seps: &[&str]) -> String {
+    let mut out = String::new();
+    for (i, code) in codes.iter().enumerate() {
+        if i > 0 {
+            out.push_str(seps[i - 1]);
+        }
+        out.push_str(code);
+    }
+    out
+}
+
+fn year_code(s: &str) -> &'static str {
+    if s.len() == 4 { "yyyy" } else { "yy" }
+}
+
+fn month_code(s: &str) -> &'static str {
+    if s.len() == 2 { "mm" } else { "m" }
+}
+
+fn day_code(s: &str) -> &'static str {
+    if s.len() == 2 { "dd" } else { "d" }
+}
+
+fn parse_year(s: &str) -> Option<i32> {
+    let year: i32 = s.parse().ok()?;
+    match s.len() {
+        4 => Some(year),
+        1 | 2 => Some(if year < 30 { 2000 + year } else { 1900 + year }),
+        _ => None,
+    }
+}
+
+fn match_month_name(word: &str, locale: &Locale) -> Option<(u8, &'static str)> {
+    let needle = word.to_lowercase();
+    let matches = |names: &[String]| {
+        names
+            .iter()
+            .position(|name| name.trim_end_matches('.').to_lowercase() == needle)
+    };
+    if let Some(idx) = matches(&locale.mmm) {
+        return Some((idx as u8 + 1, "mmm"));
+    }
+    matches(&locale.mmmm).map(|idx| (idx as u8 + 1, "mmmm"))
+}
+
+fn date_serial(year: i32, month: u8, day: u8, options: &FormatterOptions) -> Option<f64> {
+    if day == 0 {
+        return None;
+    }
+    let date = DateValue::new(year).with_month(month).with_day(day);
+    date_to_serial(&date, options).ok()
+}
+
+struct TimeMatch {
+    fraction: f64,
+    format: String,
+    elapsed: bool,
+}
+
+fn match_time(s: &str, locale: &Locale) -> Option<TimeMatch> {
+    let (body, pm) = match take_ampm(s, locale) {
+        Some((rest, pm)) => (rest, Some(pm)),
+        None => (s, None),
+    };
+
+    let mut fields = body.split(':');
+    let hours = fields.next()?;
+    let minutes = fields.next();
+    let seconds = fields.next();
+    if fields.next().is_some() || !is_digits(hours) || (minutes.is_none() && pm.is_none()) {
+        return None;
+    }
+
+    let mut hour: u32 = hours.parse().ok()?;
+    let minute: u32 = match minutes {
+        Some(m) if is_digits(m) && m.len() <= 2 => m.parse().ok()?,
+        Some(_) => return None,
+        None => 0,
+    };
+    if minute >= 60 {
+        return None;
+    }
+
+    let mut second = 0.0;
+    let mut sec_decimals = 0;
+    if let Some(sec) = seconds {
+        let (whole, frac) = match sec.split_once(['.', ',']) {
+            Some((whole, frac)) if is_digits(frac) => (whole, frac),
+            Some(_) => return None,
+            None => (sec, ""),
+        };
+        if !is_digits(whole) || whole.len() > 2 {
+            return None;
+        }
+        second = format!("{whole}.{frac}0").parse::<f64>().ok()?;
+        if second >= 60.0 {
+            return None;
+        }
+        sec_decimals = frac.len().min(3);
+    }
+
+    let mut elapsed = false;
+    match pm {
+        Some(pm) => {
+            if hour == 0 || hour > 12 {
+                return None;
+            }
+            hour %= 12;
+            if pm {
+                hour += 12;
+            }
+        }
+        None if hour >= 24 => elapsed = true,
+        None => {}
+    }
+
+    let mut format = String::from(if elapsed { "[h]" } else { "h" });
+    if minutes.is_some() {
+        format.push_str(":mm");
+    }
+    if seconds.is_some() {
+        format.push_str(":ss");
+        if sec_decimals > 0 {
+            format.push('.');
+            format.push_str(&"0".repeat(sec_decimals));
+        }
+    }
+    if pm.is_some() {
+        format.push_str(" AM/PM");
+    }
+
+    let total = hour as f64 * 3600.0 + minute as f64 * 60.0 + second;
+    Some(TimeMatch {
+        fraction: total / DAYSIZE,
+        format,
+        elapsed,
+    })
+}
+
+fn take_ampm<'a>(s: &'a str, locale: &Locale) -> Option<(&'a str, bool)> {
+    let lower = s.to_lowercase();
+    // locales may define fewer than two markers
+    let localized = [(locale.ampm.first(), false), (locale.ampm.get(1), true)]
+        .into_iter()
+        .filter_map(|(marker, pm)| marker.map(|m| (m.to_lowercase(), pm)));
+    let candidates = localized.chain([
+        ("am".to_string(), false),
+        ("pm".to_string(), true),
+        ("a".to_string(), false),
+        ("p".to_string(), true),
+    ]);
+    for (marker, pm) in candidates {
+        if marker.is_empty() {
+            continue;
+        }
+        if lower.ends_with(marker.as_str()) && lower.len() == s.len() {
+            let rest = s[..s.len() - marker.len()].trim_end();
+            if rest.ends_with(|c: char| c.is_ascii_digit()) {
+                return Some((rest, pm));
+            }
+        }
+        if lower.starts_with(marker.as_str()) && lower.len() == s.len() {
+            let rest = s[marker.len()..].trim_start();
+            if rest.starts_with(|c: char| c.is_ascii_digit()) {
+                return Some((rest, pm));
+            }
+        }
+    }
+    None
+}
@@ -259,6 +259,24 @@ fn parse_formatter_options(options: &[u8]) -> Result<crate::FormatterOptions, St
                         formatter_options.bigint_error_number = b;
                     }
                 }
+                "rounding" => {
+                    let mode = match value.as_str() {
+                        Some("half_away_from_zero") => Some(crate::RoundingMode::HalfAwayFromZero),
+                        Some("half_even") => Some(crate::RoundingMode::HalfEven),
+                        Some("truncate") => Some(crate::RoundingMode::Truncate),
+                        Some("floor") => Some(crate::RoundingMode::Floor),
+                        Some("ceiling") => Some(crate::RoundingMode::Ceiling),
+                        _ => None,
+                    };
+                    if let Some(mode) = mode {
+                        formatter_options.rounding = mode;
+                    }
+                }
+                "bigint_exact" => {
+                    if let Some(b) = value.as_bool() {
+                        formatter_options.bigint_exact = b;
+                    }
+                }
                 "date_span_large" => {
                     if let Some(b) = value.as_bool() {
                         formatter_options.date_span_large = b;
@@ -284,6 +302,11 @@ fn parse_formatter_options(options: &[u8]) -> Result<crate::FormatterOptions, St
                         formatter_options.ignore_timezone = b;
                     }
                 }
+                "timezone" => {
+                    if let Some(name) = value.as_str() {
+                        formatter_options.timezone = Some(crate::TimeZone::named(name));
+                    }
+                }
                 "index_colors" => {
                     if let Some(b) = value.as_bool() {
                         formatter_options.index_colors = b;
@@ -314,6 +337,26 @@ fn parse_formatter_options(options: &[u8]) -> Result<crate::FormatterOptions, St
                         formatter_options.fill_char = Some(s.to_string());
                     }
                 }
+                "width" => {
+                    if let Some(n) = value.as_u64() {
+                        formatter_options.width = usize::try_from(n).ok();
+                    }
+                }
+                "general_width" => {
+                    if let Some(n) = value.as_u64() {
+                        formatter_options.general_width = usize::try_from(n).ok();
+                    }
+                }
+                "native_digits" => {
+                    if let Some(b) = value.as_bool() {
+                        formatter_options.native_digits = b;
+                    }
+                }
+                "date_1904" => {
+                    if let Some(b) = value.as_bool() {
+                        formatter_options.date_1904 = b;
+                    }
+                }
                 _ => {
                     // Ignore unknown options
                 }
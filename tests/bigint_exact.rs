@@ -0,0 +1,55 @@
+use num_bigint::BigInt;
+use numfmt_rs::{FormatterOptions, format_with_options};
+
+fn fmt(pattern: &str, value: &str) -> String {
+    let value: BigInt = value.parse().unwrap();
+    let opts = FormatterOptions::default().with_bigint_exact(true);
+    format_with_options(pattern, value, opts).unwrap()
+}
+
+const LEDGER: &str = "170141183460469231731687303715884105727";
+
+#[test]
+fn groups_and_pads_large_integers() {
+    assert_eq!(
+        fmt("#,##0", LEDGER),
+        "170,141,183,460,469,231,731,687,303,715,884,105,727"
+    );
+    assert_eq!(fmt("0.00", "9007199254740993"), "9007199254740993.00");
+    assert_eq!(
+        fmt("#,##0;(#,##0)", "-12345678901234567890"),
+        "(12,345,678,901,234,567,890)"
+    );
+    assert_eq!(
+        fmt("000000000000000000000", "12345678901234567890"),
+        "012345678901234567890"
+    );
+    assert_eq!(fmt("#0-000-00", "9007199254750000"), "90071992547-500-00");
+}
+
+#[test]
+fn scales_large_integers() {
+    assert_eq!(fmt("0%", "9007199254750000"), "900719925475000000%");
+    assert_eq!(fmt("#,##0,,", "123456789012345678"), "123,456,789,012");
+    assert_eq!(fmt("0.0,,,", "98765432109876543"), "98765432.1");
+}
+
+#[test]
+fn renders_large_integers_in_scientific_and_general() {
+    assert_eq!(fmt("0.000E+00", LEDGER), "1.701E+38");
+    assert_eq!(fmt("0.00E+00", "-99999999999999999999"), "-1.00E+20");
+    assert_eq!(fmt("##0.0E+0", "123456789012345678901"), "123.5E+18");
+    assert_eq!(fmt("General", LEDGER), "1.70141E+38");
+    assert_eq!(fmt("General", "12345678901"), "12345678901");
+    let huge = format!("1{}", "0".repeat(400));
+    assert_eq!(fmt("0.0E+0", &huge), "1.0E+400");
+}
+
+#[test]
+fn keeps_overflow_without_the_option() {
+    let value: BigInt = LEDGER.parse().unwrap();
+    assert_eq!(
+        format_with_options("#,##0", value, FormatterOptions::default()).unwrap(),
+        "######"
+    );
+}
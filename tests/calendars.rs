@@ -0,0 +1,38 @@
+use numfmt_rs::{DateValue, FormatValue, format};
+
+fn day(year: i32, month: u8, day: u8) -> FormatValue<'static> {
+    FormatValue::Date(DateValue::new(year).with_month(month).with_day(day))
+}
+
+#[test]
+fn hebrew_calendar() {
+    let pattern = "[$-80000]d mmmm yyyy";
+    assert_eq!(format(pattern, day(2023, 9, 16)).unwrap(), "1 Tishri 5784");
+    assert_eq!(format(pattern, day(2024, 3, 1)).unwrap(), "21 Adar I 5784");
+    assert_eq!(
+        format(pattern, day(2024, 3, 25)).unwrap(),
+        "15 Adar II 5784"
+    );
+    assert_eq!(format(pattern, day(2025, 3, 14)).unwrap(), "14 Adar 5785");
+    assert_eq!(
+        format("[$-80000]yyyy-mm-dd", day(2024, 4, 23)).unwrap(),
+        "5784-08-15"
+    );
+    assert_eq!(
+        format("[$-8040D]d mmmm yyyy", day(2024, 4, 23)).unwrap(),
+        "15 ניסן 5784"
+    );
+}
+
+#[test]
+fn solar_year_calendars() {
+    let date = || day(2024, 12, 21);
+    assert_eq!(format("[$-30000]yy.mm.dd", date()).unwrap(), "06.12.21");
+    assert_eq!(format("[$-30411]ggge年m月", date()).unwrap(), "令和6年12月");
+    assert_eq!(format("[$-30000]yy", day(1989, 1, 7)).unwrap(), "64");
+    assert_eq!(format("[$-30000]yy", day(1989, 1, 8)).unwrap(), "01");
+    assert_eq!(format("[$-40000]yyyy/mm/dd", date()).unwrap(), "113/12/21");
+    assert_eq!(format("[$-50000]yyyy-mm-dd", date()).unwrap(), "4357-12-21");
+    assert_eq!(format("[$-70000]d/m/yyyy", date()).unwrap(), "21/12/2567");
+    assert_eq!(format("[$-10000]yyyy-mm-dd", date()).unwrap(), "2024-12-21");
+}
@@ -0,0 +1,89 @@
+use numfmt_rs::{
+    CellLayout, CompiledFormat, FormattedPart, FormatterOptions, PartKind, format_layout,
+};
+
+const ACCOUNTING: &str = "_-* #,##0.00_-;-* #,##0.00_-;_-* \"-\"??_-;_-@_-";
+
+fn part(kind: PartKind, text: &str) -> FormattedPart {
+    FormattedPart {
+        kind,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn lays_out_accounting_sections() {
+    let opts = FormatterOptions::default();
+    let positive = format_layout(ACCOUNTING, 1234.5, opts.clone()).unwrap();
+    assert_eq!(
+        positive,
+        CellLayout {
+            leading_skip: 1,
+            prefix: vec![],
+            fill: Some(" ".to_string()),
+            suffix: vec![
+                part(PartKind::Integer, "1"),
+                part(PartKind::Group, ","),
+                part(PartKind::Integer, "234"),
+                part(PartKind::Decimal, "."),
+                part(PartKind::Fraction, "50"),
+            ],
+            trailing_skip: 1,
+        }
+    );
+    assert_eq!(positive.min_width(), 10);
+
+    let negative = format_layout(ACCOUNTING, -42.0, opts.clone()).unwrap();
+    assert_eq!(negative.leading_skip, 0);
+    assert_eq!(negative.prefix, [part(PartKind::MinusSign, "-")]);
+    assert_eq!(
+        negative.suffix.last(),
+        Some(&part(PartKind::Fraction, "00"))
+    );
+    assert_eq!(negative.trailing_skip, 1);
+
+    let zero = format_layout(ACCOUNTING, 0.0, opts.clone()).unwrap();
+    assert_eq!(
+        zero.suffix,
+        [part(PartKind::Literal, "-"), part(PartKind::Integer, "  ")]
+    );
+
+    let text = format_layout(ACCOUNTING, "abc", opts).unwrap();
+    assert_eq!(text.fill, None);
+    assert_eq!(text.prefix, [part(PartKind::Text, "abc")]);
+    assert_eq!((text.leading_skip, text.trailing_skip), (1, 1));
+}
+
+#[test]
+fn measures_wide_skips_and_signs() {
+    let opts = FormatterOptions::default();
+    let layout = format_layout("_(\"$\"* #,##0_中", 1_000_000.0, opts.clone()).unwrap();
+    assert_eq!(layout.prefix, [part(PartKind::Currency, "$")]);
+    assert_eq!(layout.fill.as_deref(), Some(" "));
+    assert_eq!(layout.trailing_skip, 2);
+    assert_eq!(layout.min_width(), 13);
+
+    // a single-section format writes the minus sign before its skip
+    let negative = format_layout("_(#,##0_)", -5.0, opts).unwrap();
+    assert_eq!(negative.leading_skip, 1);
+    assert_eq!(
+        negative.prefix,
+        [part(PartKind::MinusSign, "-"), part(PartKind::Integer, "5")]
+    );
+    assert_eq!(negative.trailing_skip, 1);
+}
+
+#[test]
+fn applies_width_but_leaves_the_fill() {
+    let opts = |width| FormatterOptions::default().with_width(Some(width));
+    let general = format_layout("General", 1.23456789, opts(6)).unwrap();
+    assert_eq!(general.prefix, [part(PartKind::General, "1.2346")]);
+    let overflow = format_layout("0.00", 1234.5, opts(5)).unwrap();
+    assert_eq!(overflow.prefix, [part(PartKind::Overflow, "######")]);
+
+    let compiled = CompiledFormat::new("0*.", opts(5)).unwrap();
+    let layout = compiled.format_layout(7.0).unwrap();
+    assert_eq!(layout.prefix, [part(PartKind::Integer, "7")]);
+    assert_eq!(layout.fill.as_deref(), Some("."));
+    assert!(layout.suffix.is_empty());
+}
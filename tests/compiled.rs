@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use std::thread;
+
+use numfmt_rs::{ColorValue, CompiledFormat, FormatterOptions, format_color, format_with_options};
+
+#[test]
+fn formats_like_format_with_options() {
+    let opts = FormatterOptions::default().with_locale("de");
+    let pattern = "[Red]#,##0.00;[Blue]-#,##0.00;0;\"text: \"@";
+    let compiled = CompiledFormat::new(pattern, opts.clone()).unwrap();
+    for value in [1234.5, -0.25, 0.0] {
+        assert_eq!(
+            compiled.format(value).unwrap(),
+            format_with_options(pattern, value, opts.clone()).unwrap()
+        );
+        assert_eq!(
+            compiled.color(value),
+            format_color(pattern, value, opts.clone()).unwrap()
+        );
+    }
+    assert_eq!(compiled.format("abc").unwrap(), "text: abc");
+    assert_eq!(
+        compiled.color(-1.0),
+        Some(ColorValue::String("blue".into()))
+    );
+}
+
+#[test]
+fn invalid_patterns_follow_throws() {
+    let lenient = FormatterOptions {
+        throws: false,
+        ..FormatterOptions::default()
+    };
+    assert!(CompiledFormat::new("0.0.0;;;;", FormatterOptions::default()).is_err());
+    let compiled = CompiledFormat::new("0.0.0;;;;", lenient.clone()).unwrap();
+    assert_eq!(
+        compiled.format(1.0).unwrap(),
+        format_with_options("0.0.0;;;;", 1.0, lenient).unwrap()
+    );
+}
+
+#[test]
+fn shares_across_threads() {
+    let compiled = Arc::new(CompiledFormat::new("0.0%", FormatterOptions::default()).unwrap());
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let compiled = Arc::clone(&compiled);
+            thread::spawn(move || {
+                let mut out = String::new();
+                for j in 0..100 {
+                    out.clear();
+                    compiled
+                        .format_into(&mut out, (i * 100 + j) as f64 / 1000.0)
+                        .unwrap();
+                }
+                out
+            })
+        })
+        .collect();
+    let last: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(last, ["9.9%", "19.9%", "29.9%", "39.9%"]);
+}
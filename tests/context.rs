@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use numfmt_rs::{
+    FormatterOptions, LocaleRegistry, LocaleSettings, NumfmtContext, format, locale_tags,
+};
+
+fn tenant(decimal: &str) -> NumfmtContext {
+    let context = NumfmtContext::new().with_options(FormatterOptions::default().with_locale("qc"));
+    let settings = LocaleSettings {
+        decimal: Some(decimal.to_string()),
+        ..LocaleSettings::default()
+    };
+    context.add_locale(settings, "qc").unwrap();
+    context
+}
+
+#[test]
+fn contexts_keep_their_own_locales() {
+    let (first, second) = (tenant(","), tenant("'"));
+    assert_eq!(first.format("0.00", 1.5).unwrap(), "1,50");
+    assert_eq!(second.format("0.00", 1.5).unwrap(), "1'50");
+    assert_eq!(first.display("[$-qc]0.0", 2.5).to_string(), "2,5");
+    assert_eq!(format("[$-qc]0.0", 2.5).unwrap(), "2.5");
+    assert!(!locale_tags().contains(&"qc".to_string()));
+
+    assert!(first.remove_locale("qc"));
+    assert_eq!(first.format("0.00", 1.5).unwrap(), "1.50");
+    assert_eq!(second.compile("0.0").unwrap().format(3.5).unwrap(), "3'5");
+}
+
+#[test]
+fn contexts_own_their_pattern_cache() {
+    let context = NumfmtContext::new().with_cache_capacity(2);
+    for pattern in ["0", "0.0", "0.00", "0.0"] {
+        context.format(pattern, 1.0).unwrap();
+    }
+    let stats = context.cache().stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 3, 2));
+    assert_eq!(stats.evictions, 1);
+}
+
+#[test]
+fn cache_evicts_the_least_recently_used_pattern() {
+    // two patterns per shard, so the hot one always outlives the cold one
+    let context = NumfmtContext::new().with_cache_capacity(32);
+    context.format("0.00", 1.0).unwrap();
+    for i in 0..200 {
+        context.format(&format!("0\"{i}\""), 1.0).unwrap();
+        context.format("0.00", 1.0).unwrap();
+    }
+    let stats = context.cache().stats();
+    assert_eq!((stats.hits, stats.misses), (200, 201), "{stats:?}");
+    assert!(stats.entries <= 32, "{stats:?}");
+}
+
+#[test]
+fn contexts_can_share_a_registry() {
+    let registry = Arc::new(LocaleRegistry::new());
+    let a = NumfmtContext::new().with_locales(registry.clone());
+    let b = NumfmtContext::new().with_locales(registry);
+    a.add_locale(
+        LocaleSettings {
+            group: Some("_".to_string()),
+            ..LocaleSettings::default()
+        },
+        "qd",
+    )
+    .unwrap();
+    let opts = FormatterOptions::default().with_locale("qd");
+    assert_eq!(
+        b.format_with_options("#,##0", 1234567, &opts).unwrap(),
+        "1_234_567"
+    );
+}
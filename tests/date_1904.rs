@@ -0,0 +1,57 @@
+use numfmt_rs::{DateValue, FormatValue, FormatterOptions, format_with_options, parse_date};
+
+fn opts() -> FormatterOptions {
+    FormatterOptions::default().with_date_1904(true)
+}
+
+#[test]
+fn serials_count_from_1904() {
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", 0.0, opts()).unwrap(),
+        "1904-01-01"
+    );
+    assert_eq!(
+        format_with_options("dddd d mmm yyyy", 1.0, opts()).unwrap(),
+        "Saturday 2 Jan 1904"
+    );
+    assert_eq!(
+        format_with_options("yyyy-mm-dd hh:mm", 43890.5, opts()).unwrap(),
+        "2024-03-01 12:00"
+    );
+    assert_eq!(
+        format_with_options("B2yyyy-mm-dd", 43890.0, opts()).unwrap(),
+        "1445-08-21"
+    );
+}
+
+#[test]
+fn date_values_serialize_against_1904() {
+    let date = || FormatValue::Date(DateValue::new(2024).with_month(3).with_day(1));
+    assert_eq!(
+        format_with_options("General", date(), opts()).unwrap(),
+        "43890"
+    );
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", date(), opts()).unwrap(),
+        "2024-03-01"
+    );
+    let parsed = parse_date("2024-03-01", &opts()).unwrap();
+    assert_eq!(parsed.value, FormatValue::Number(43890.0));
+}
+
+#[test]
+fn bounds_follow_the_epoch() {
+    let strict = FormatterOptions {
+        date_span_large: false,
+        date_error_number: false,
+        ..opts()
+    };
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", 2957003.0, strict.clone()).unwrap(),
+        "9999-12-31"
+    );
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", 2957004.0, strict).unwrap(),
+        "######"
+    );
+}
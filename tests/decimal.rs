@@ -0,0 +1,40 @@
+#![cfg(feature = "rust_decimal")]
+
+use numfmt_rs::format;
+use rust_decimal::Decimal;
+
+fn dec(text: &str) -> Decimal {
+    text.parse().unwrap()
+}
+
+#[test]
+fn formats_decimals_without_binary_artifacts() {
+    assert_eq!(format("0.00", dec("0.1") + dec("0.2")).unwrap(), "0.30");
+    assert_eq!(
+        format("#,##0.00", dec("1234567890123.45")).unwrap(),
+        "1,234,567,890,123.45"
+    );
+    assert_eq!(
+        format("0.0000000000", dec("12345678.0123456789")).unwrap(),
+        "12345678.0123456789"
+    );
+    assert_eq!(format("0.0", dec("1.25")).unwrap(), "1.3");
+    assert_eq!(format("0.00", dec("1.005")).unwrap(), "1.01");
+    assert_eq!(format("0.0#", dec("-2.50")).unwrap(), "-2.5");
+    assert_eq!(format("0", dec("-0.4")).unwrap(), "0");
+    assert_eq!(format("@", dec("1.10")).unwrap(), "1.1");
+}
+
+#[test]
+fn scales_decimals_exactly() {
+    assert_eq!(format("0.0%", dec("0.0005")).unwrap(), "0.1%");
+    assert_eq!(format("0.00%", dec("12.3456789")).unwrap(), "1234.57%");
+    assert_eq!(
+        format("#,##0.000,,", dec("9876543500")).unwrap(),
+        "9,876.544"
+    );
+    assert_eq!(
+        format("0.00;(0.00)", dec("-1234.565")).unwrap(),
+        "(1234.57)"
+    );
+}
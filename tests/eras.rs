@@ -0,0 +1,52 @@
+use numfmt_rs::{DateValue, FormatValue, FormatterOptions, format, format_with_options};
+
+fn day(year: i32, month: u8, day: u8) -> FormatValue<'static> {
+    FormatValue::Date(DateValue::new(year).with_month(month).with_day(day))
+}
+
+#[test]
+fn japanese_imperial_eras() {
+    assert_eq!(
+        format("[$-411]ggge\"年\"m\"月\"d\"日\"", day(2024, 12, 21)).unwrap(),
+        "令和6年12月21日"
+    );
+    assert_eq!(
+        format("[$-411]gee.mm.dd", day(2024, 12, 21)).unwrap(),
+        "R06.12.21"
+    );
+    assert_eq!(format("[$-411]gge", day(1989, 1, 7)).unwrap(), "昭64");
+    assert_eq!(format("[$-411]gge", day(1989, 1, 8)).unwrap(), "平1");
+    assert_eq!(format("[$-411]ggge", day(1912, 7, 30)).unwrap(), "大正1");
+    assert_eq!(
+        format_with_options(
+            "ggge",
+            day(2019, 4, 30),
+            FormatterOptions::default().with_locale("ja")
+        )
+        .unwrap(),
+        "平成31"
+    );
+}
+
+#[test]
+fn gannen_marks_first_era_year() {
+    let pattern = "[$-ja-JP-x-gannen]ggge\"年\"";
+    assert_eq!(format(pattern, day(2019, 5, 1)).unwrap(), "令和元年");
+    assert_eq!(format(pattern, day(2020, 1, 1)).unwrap(), "令和2年");
+}
+
+#[test]
+fn minguo_and_buddhist_eras() {
+    assert_eq!(
+        format("[$-404]ggge", day(2024, 1, 1)).unwrap(),
+        "中華民國113"
+    );
+    assert_eq!(format("[$-404]ge", day(1912, 1, 1)).unwrap(), "民國1");
+    assert_eq!(format("[$-41E]g e", day(2024, 1, 1)).unwrap(), "พ.ศ. 2567");
+}
+
+#[test]
+fn eras_without_calendar_data() {
+    assert_eq!(format("ge", day(2024, 1, 1)).unwrap(), "2024");
+    assert_eq!(format("[$-411]ge", day(1800, 6, 1)).unwrap(), "1800");
+}
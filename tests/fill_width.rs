@@ -0,0 +1,40 @@
+use numfmt_rs::{FormatterOptions, PartKind, format_parts, format_with_options};
+use unicode_width::UnicodeWidthStr;
+
+fn fmt(pattern: &str, value: f64, width: Option<usize>) -> String {
+    let opts = FormatterOptions::default().with_width(width);
+    format_with_options(pattern, value, opts).unwrap()
+}
+
+#[test]
+fn fill_expands_to_width() {
+    let accounting = "_($* #,##0.00_);_($* (#,##0.00);_($* \"-\"??_);_(@_)";
+    assert_eq!(fmt(accounting, 1234.5, Some(16)), " $     1,234.50 ");
+    assert_eq!(fmt(accounting, -42.0, Some(16)), " $       (42.00)");
+    assert_eq!(fmt(accounting, 0.0, Some(16)), " $          -   ");
+    for value in [1234.5, -42.0, 0.0, 9_876_543.21] {
+        assert_eq!(fmt(accounting, value, Some(20)).width(), 20, "{value}");
+    }
+    // without a width the fill writes nothing, as before
+    assert_eq!(fmt(accounting, 1234.5, None), " $1,234.50 ");
+    // no room left means no fill
+    assert_eq!(fmt("0*x", 123.0, Some(3)), "123");
+    assert_eq!(fmt("0*x", 1.0, Some(4)), "1xxx");
+    // only the first fill of a section expands
+    assert_eq!(fmt("*-0*=", 7.0, Some(4)), "---7");
+}
+
+#[test]
+fn widths_count_terminal_cells() {
+    assert_eq!(fmt("0*中", 12.0, Some(6)), "12中中");
+    // an odd cell left over is padded with a space
+    assert_eq!(fmt("0*中", 12.0, Some(7)), "12中中 ");
+    assert_eq!(fmt("\"合计\"* 0", 5.0, Some(8)), "合计   5");
+    assert_eq!(fmt("_中0", 5.0, Some(3)), "  5");
+    assert_eq!(fmt("_中0", 5.0, None), " 5");
+
+    let opts = FormatterOptions::default().with_width(Some(6));
+    let parts = format_parts("0*.", 42.0, opts).unwrap();
+    assert_eq!(parts[1].kind, PartKind::Fill);
+    assert_eq!(parts[1].text, "....");
+}
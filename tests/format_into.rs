@@ -0,0 +1,89 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Write;
+
+use numfmt_rs::{Formatted, FormatterOptions, format_into, format_with_options};
+
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn matches_format_with_options() {
+    let opts = FormatterOptions::default();
+    let cases: [(&str, f64); 6] = [
+        ("#,##0.00", -1234567.891),
+        ("0.0E+00", 0.000123),
+        ("# ?/?", 3.25),
+        ("General", 1.0 / 3.0),
+        ("yyyy-mm-dd hh:mm:ss.000", 45000.123456),
+        ("[DBNum1]0.00", 12.5),
+    ];
+    for (pattern, value) in cases {
+        let expected = format_with_options(pattern, value, opts.clone()).unwrap();
+        let mut out = String::from(">");
+        format_into(&mut out, pattern, value, &opts).unwrap();
+        assert_eq!(out, format!(">{expected}"), "{pattern}");
+        assert_eq!(
+            Formatted::new(pattern, value, &opts).to_string(),
+            expected,
+            "{pattern}"
+        );
+    }
+}
+
+#[test]
+fn writes_through_display() {
+    let opts = FormatterOptions::default();
+    let mut out = String::new();
+    for value in [1.5, -2.0] {
+        write!(out, "[{}]", Formatted::new("0.00;(0.00)", value, &opts)).unwrap();
+    }
+    assert_eq!(out, "[1.50][(2.00)]");
+}
+
+#[test]
+fn numbers_format_without_allocating() {
+    let opts = FormatterOptions::default();
+    let mut out = String::with_capacity(256);
+    let patterns = [
+        "#,##0.00",
+        "0.000E+00",
+        "0%",
+        "# ??/??",
+        "General",
+        "yyyy-mm-dd hh:mm:ss.00",
+    ];
+    // warm the pattern cache
+    for pattern in patterns {
+        format_into(&mut out, pattern, 1.0, &opts).unwrap();
+    }
+    for pattern in patterns {
+        for value in [0.0, 1234567.891, -0.0625, 1e-7] {
+            out.clear();
+            let before = allocations();
+            format_into(&mut out, pattern, value, &opts).unwrap();
+            assert_eq!(allocations(), before, "{pattern} {value} -> {out}");
+        }
+    }
+}
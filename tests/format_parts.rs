@@ -0,0 +1,153 @@
+use numfmt_rs::{
+    CompiledFormat, FormattedPart, FormatterOptions, PartKind, format_parts, format_with_options,
+};
+
+fn kinds(parts: &[FormattedPart]) -> Vec<(PartKind, &str)> {
+    parts
+        .iter()
+        .map(|part| (part.kind, part.text.as_str()))
+        .collect()
+}
+
+#[test]
+fn tags_number_parts() {
+    let opts = FormatterOptions::default();
+    let parts = format_parts("#,##0.00;(#,##0.00)", -1234.5, opts.clone()).unwrap();
+    assert_eq!(
+        kinds(&parts),
+        [
+            (PartKind::Literal, "("),
+            (PartKind::Integer, "1"),
+            (PartKind::Group, ","),
+            (PartKind::Integer, "234"),
+            (PartKind::Decimal, "."),
+            (PartKind::Fraction, "50"),
+            (PartKind::Literal, ")"),
+        ]
+    );
+
+    let parts = format_parts("[$€-407] 0.0%", -0.25, opts.clone()).unwrap();
+    assert_eq!(
+        kinds(&parts),
+        [
+            (PartKind::MinusSign, "-"),
+            (PartKind::Currency, "€"),
+            (PartKind::Literal, " "),
+            (PartKind::Integer, "25"),
+            (PartKind::Decimal, ","),
+            (PartKind::Fraction, "0"),
+            (PartKind::Percent, "%"),
+        ]
+    );
+
+    let parts = format_parts("0.00E+00", 0.000123, opts.clone()).unwrap();
+    assert_eq!(
+        kinds(&parts),
+        [
+            (PartKind::Integer, "1"),
+            (PartKind::Decimal, "."),
+            (PartKind::Fraction, "23"),
+            (PartKind::ExponentSeparator, "E"),
+            (PartKind::ExponentSign, "-"),
+            (PartKind::ExponentInteger, "04"),
+        ]
+    );
+
+    let parts = format_parts("# ?/?", 1.5, opts).unwrap();
+    assert_eq!(
+        kinds(&parts),
+        [
+            (PartKind::Integer, "1"),
+            (PartKind::Literal, " "),
+            (PartKind::Numerator, "1"),
+            (PartKind::FractionSlash, "/"),
+            (PartKind::Denominator, "2"),
+        ]
+    );
+}
+
+#[test]
+fn tags_fill_skip_and_dates() {
+    let opts = FormatterOptions::default().with_fill_char(Some(String::new()));
+    let parts = format_parts("_(\"$\"* #,##0_)", 12.0, opts).unwrap();
+    assert_eq!(
+        kinds(&parts),
+        [
+            (PartKind::Skip, " "),
+            (PartKind::Currency, "$"),
+            (PartKind::Fill, " "),
+            (PartKind::Integer, "12"),
+            (PartKind::Skip, " "),
+        ]
+    );
+
+    let opts = FormatterOptions::default();
+    let parts = format_parts("yyyy-mm-dd hh:mm:ss.0 AM/PM", 45000.75, opts.clone()).unwrap();
+    assert_eq!(
+        kinds(&parts),
+        [
+            (PartKind::Year, "2023"),
+            (PartKind::Literal, "-"),
+            (PartKind::Month, "03"),
+            (PartKind::Literal, "-"),
+            (PartKind::Day, "15"),
+            (PartKind::Literal, " "),
+            (PartKind::Hour, "06"),
+            (PartKind::Literal, ":"),
+            (PartKind::Minute, "00"),
+            (PartKind::Literal, ":"),
+            (PartKind::Second, "00"),
+            (PartKind::Decimal, "."),
+            (PartKind::FractionalSecond, "0"),
+            (PartKind::Literal, " "),
+            (PartKind::DayPeriod, "PM"),
+        ]
+    );
+
+    let parts = format_parts("0;0;0;\"<\"@\">\"", "abc", opts).unwrap();
+    assert_eq!(
+        kinds(&parts),
+        [
+            (PartKind::Literal, "<"),
+            (PartKind::Text, "abc"),
+            (PartKind::Literal, ">"),
+        ]
+    );
+}
+
+#[test]
+fn parts_join_to_formatted_string() {
+    let opts = FormatterOptions::default();
+    let cases: &[(&str, f64)] = &[
+        ("General", -1234.5678),
+        ("0.0;[Red]-0.0;\"zero\"", 0.0),
+        ("[h]:mm:ss", -1.25),
+        ("#,##0,,\"M\"", 12_345_678.0),
+        ("[<0]\"neg\";0", f64::NAN),
+        ("0", f64::NEG_INFINITY),
+        ("[$-th-TH,D07]#,##0.00", 1234.5),
+        ("[DBNum1]0", 42.0),
+        ("0;;;", -1.0),
+    ];
+    for &(pattern, value) in cases {
+        let parts = format_parts(pattern, value, opts.clone()).unwrap();
+        let joined: String = parts.iter().map(|part| part.text.as_str()).collect();
+        assert_eq!(
+            joined,
+            format_with_options(pattern, value, opts.clone()).unwrap(),
+            "{pattern}"
+        );
+        assert!(parts.iter().all(|part| !part.text.is_empty()), "{pattern}");
+    }
+
+    let compiled = CompiledFormat::new("0.0", opts).unwrap();
+    assert_eq!(
+        kinds(&compiled.format_parts(-2.0).unwrap()),
+        [
+            (PartKind::MinusSign, "-"),
+            (PartKind::Integer, "2"),
+            (PartKind::Decimal, "."),
+            (PartKind::Fraction, "0"),
+        ]
+    );
+}
@@ -0,0 +1,69 @@
+use numfmt_rs::{FormatterOptions, format_general_with_width, format_with_options};
+
+#[test]
+fn narrows_general_to_a_width() {
+    let value = 1.23456789;
+    assert_eq!(
+        format_general_with_width(value, 11, "").as_deref(),
+        Some("1.23456789")
+    );
+    assert_eq!(
+        format_general_with_width(value, 6, "").as_deref(),
+        Some("1.2346")
+    );
+    assert_eq!(
+        format_general_with_width(-value, 6, "").as_deref(),
+        Some("-1.235")
+    );
+    assert_eq!(
+        format_general_with_width(value, 6, "de").as_deref(),
+        Some("1,2346")
+    );
+    assert_eq!(
+        format_general_with_width(12_345_678_901.0, 5, "").as_deref(),
+        Some("1E+10")
+    );
+    assert_eq!(
+        format_general_with_width(12_345_678_901.0, 8, "").as_deref(),
+        Some("1.23E+10")
+    );
+    assert_eq!(format_general_with_width(12_345_678_901.0, 4, ""), None);
+    assert_eq!(format_general_with_width(f64::NAN, 10, ""), None);
+}
+
+#[test]
+fn option_applies_to_every_general_token() {
+    let opts = FormatterOptions::default().with_general_width(Some(4));
+    let fmt =
+        |pattern: &str, value: f64| format_with_options(pattern, value, opts.clone()).unwrap();
+    assert_eq!(fmt("General", 1.23456789), "1.23");
+    assert_eq!(fmt("General", -1.23456789), "-1.2");
+    // literals of an explicit negative section are not part of the budget
+    assert_eq!(fmt("General\" m\";(General)", -98.765), "(98.8)");
+    assert_eq!(fmt("0.000", 1.23456789), "1.235");
+    // too wide for the budget keeps the usual form
+    assert_eq!(fmt("General", 1e100), "1E+100");
+
+    let both = opts.with_width(Some(4));
+    assert_eq!(
+        format_with_options("General", -1.23456789, both).unwrap(),
+        "-1.2"
+    );
+}
+
+#[test]
+fn option_matches_the_function_for_negative_values() {
+    for value in [-1.23456789, -98.765, -0.000012345, -12_345_678_901.0, -7.0] {
+        for width in 3..12 {
+            let opts = FormatterOptions::default().with_general_width(Some(width));
+            let expected = format_general_with_width(value, width, "").unwrap_or_else(|| {
+                format_with_options("General", value, Default::default()).unwrap()
+            });
+            assert_eq!(
+                format_with_options("General", value, opts).unwrap(),
+                expected,
+                "{value} in {width}"
+            );
+        }
+    }
+}
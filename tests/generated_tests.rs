@@ -5,18 +5,21 @@ use serde_json::Value as JsonValue;
 use std::str::FromStr;
 use std::sync::Once;
 
+fn test_locale() -> LocaleSettings {
+    LocaleSettings {
+        decimal: Some("·".to_string()),
+        positive: Some("ᐩ".to_string()),
+        negative: Some("÷".to_string()),
+        percent: Some("٪".to_string()),
+        exponent: Some("X".to_string()),
+        ..Default::default()
+    }
+}
+
 fn ensure_test_locales() {
     static INIT: Once = Once::new();
     INIT.call_once(|| {
-        let settings = LocaleSettings {
-            decimal: Some("·".to_string()),
-            positive: Some("ᐩ".to_string()),
-            negative: Some("÷".to_string()),
-            percent: Some("٪".to_string()),
-            exponent: Some("X".to_string()),
-            ..Default::default()
-        };
-        let _ = add_locale(settings, "xx");
+        let _ = add_locale(test_locale(), "xx");
     });
 }
 
@@ -163,3 +166,34 @@ fn parse_options(options_json: Option<&JsonValue>) -> FormatterOptions {
 
 // Include auto-generated test modules
 include!(concat!(env!("OUT_DIR"), "/generated_tests.rs"));
+
+/// The same fixtures formatted through a [`NumfmtContext`] with its own
+/// registry instead of the process-wide functions.
+mod through_context {
+    use super::*;
+    use numfmt_rs::{FormatValue, FormatterError, NumfmtContext};
+    use std::sync::OnceLock;
+
+    fn context() -> &'static NumfmtContext {
+        static CONTEXT: OnceLock<NumfmtContext> = OnceLock::new();
+        CONTEXT.get_or_init(|| {
+            let context = NumfmtContext::new();
+            context.add_locale(test_locale(), "xx").unwrap();
+            context
+        })
+    }
+
+    fn format(pattern: &str, value: FormatValue<'_>) -> Result<String, FormatterError> {
+        context().format(pattern, value)
+    }
+
+    fn format_with_options(
+        pattern: &str,
+        value: FormatValue<'_>,
+        options: FormatterOptions,
+    ) -> Result<String, FormatterError> {
+        context().format_with_options(pattern, value, &options)
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/generated_tests.rs"));
+}
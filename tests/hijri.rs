@@ -0,0 +1,53 @@
+use numfmt_rs::{FormatValue, FormatterError, FormatterOptions, format, format_with_options};
+
+fn fmt(pattern: &str, value: f64) -> String {
+    format(pattern, FormatValue::Number(value)).unwrap()
+}
+
+#[test]
+fn kuwaiti_conversion_across_range() {
+    assert_eq!(fmt("B2yyyy-mm-dd", 1.0), "1317-08-29");
+    assert_eq!(fmt("B2yyyy-mm-dd", 2.0), "1317-09-01");
+    assert_eq!(fmt("B2yyyy-mm-dd", 59.0), "1317-10-28");
+    assert_eq!(fmt("B2yyyy-mm-dd", 61.0), "1317-10-29");
+    assert_eq!(fmt("B2yyyy-mm-dd", 45352.0), "1445-08-21");
+    assert_eq!(fmt("B2yyyy-mm-dd", 2958465.0), "9666-04-03");
+}
+
+#[test]
+fn hijri_datetime_sections() {
+    assert_eq!(fmt("B2yyyy-mm-dd hh:mm", 45352.75), "1445-08-21 18:00");
+    assert_eq!(fmt("[$-60000]d/m/yyyy h:mm", 45000.5), "23/8/1444 12:00");
+}
+
+#[test]
+fn phantom_leap_day_is_an_error() {
+    let throws = FormatterOptions {
+        date_error_throws: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        format_with_options("B2yyyy-mm-dd", 60.0, throws),
+        Err(FormatterError::InvalidDate(_))
+    ));
+    assert_eq!(fmt("B2yyyy-mm-dd", 60.0), "60");
+
+    let overflow = FormatterOptions {
+        date_error_number: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        format_with_options("B2yyyy-mm-dd", 60.0, overflow).unwrap(),
+        "######"
+    );
+
+    // without the 1900 leap bug serial 60 is an ordinary day
+    let no_leap = FormatterOptions {
+        leap_1900: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        format_with_options("B2yyyy-mm-dd", 60.0, no_leap).unwrap(),
+        "1317-10-28"
+    );
+}
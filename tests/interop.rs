@@ -0,0 +1,104 @@
+use numfmt_rs::{DateValue, FormatterOptions};
+
+#[test]
+fn serials_convert_back_to_date_values() {
+    let opts = FormatterOptions::default();
+    assert_eq!(
+        DateValue::from_serial(45352.75, &opts).unwrap(),
+        DateValue::new(2024)
+            .with_month(3)
+            .with_day(1)
+            .with_time(18, 0, 0)
+            .with_millisecond(0)
+    );
+    let date = DateValue::from_serial(45352.0 + 0.5 / 86_400.0, &opts).unwrap();
+    assert_eq!((date.second, date.millisecond), (Some(0), Some(500)));
+    let mac = FormatterOptions::default().with_date_1904(true);
+    assert_eq!(DateValue::from_serial(0.0, &mac).unwrap().year, 1904);
+    assert!(DateValue::from_serial(f64::NAN, &opts).is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_round_trips() {
+    use chrono::{FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+    use numfmt_rs::{format, format_with_options};
+
+    let naive = NaiveDate::from_ymd_opt(2024, 3, 1)
+        .unwrap()
+        .and_hms_nano_opt(13, 45, 30, 123_456_789)
+        .unwrap();
+    assert_eq!(
+        format("yyyy-mm-dd hh:mm:ss.000", naive).unwrap(),
+        "2024-03-01 13:45:30.123"
+    );
+    let zoned = FixedOffset::east_opt(3600)
+        .unwrap()
+        .from_local_datetime(&naive)
+        .unwrap();
+    // zoned values are converted, into UTC unless a target zone is set
+    assert_eq!(format("hh:mm", zoned).unwrap(), "12:45");
+    let opts = FormatterOptions::default().with_timezone(numfmt_rs::TimeZone::Offset(3600));
+    assert_eq!(format_with_options("hh:mm", zoned, opts).unwrap(), "13:45");
+    let opts = FormatterOptions::default().with_ignore_timezone(true);
+    assert_eq!(format_with_options("hh:mm", zoned, opts).unwrap(), "13:45");
+
+    let value = DateValue::from_serial(45352.5, &FormatterOptions::default()).unwrap();
+    assert_eq!(
+        NaiveDateTime::try_from(value).unwrap(),
+        NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_round_trips() {
+    use numfmt_rs::format;
+    use time::{Date, Month, PrimitiveDateTime, Time, UtcOffset};
+
+    let date = Date::from_calendar_date(2024, Month::March, 1).unwrap();
+    let wall = PrimitiveDateTime::new(date, Time::from_hms_micro(6, 0, 0, 999_999).unwrap());
+    assert_eq!(
+        format("yyyy-mm-dd hh:mm:ss.000", wall).unwrap(),
+        "2024-03-01 06:00:00.999"
+    );
+    let offset = wall.assume_offset(UtcOffset::from_hms(-5, 0, 0).unwrap());
+    assert_eq!(format("hh:mm", offset).unwrap(), "11:00");
+
+    let value = DateValue::from_serial(45352.25, &FormatterOptions::default()).unwrap();
+    assert_eq!(
+        PrimitiveDateTime::try_from(value).unwrap(),
+        PrimitiveDateTime::new(date, Time::from_hms(6, 0, 0).unwrap())
+    );
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn jiff_round_trips() {
+    use jiff::civil::{DateTime, date};
+    use numfmt_rs::{format, format_with_options};
+
+    let wall = date(2024, 3, 1).at(13, 45, 0, 7_000_001);
+    assert_eq!(
+        format("yyyy-mm-dd hh:mm:ss.000", wall).unwrap(),
+        "2024-03-01 13:45:00.007"
+    );
+    let zoned = wall
+        .to_zoned(jiff::tz::TimeZone::fixed(jiff::tz::offset(2)))
+        .unwrap();
+    assert_eq!(format("hh:mm", zoned.clone()).unwrap(), "11:45");
+    let opts = FormatterOptions::default().with_ignore_timezone(true);
+    assert_eq!(format_with_options("hh:mm", zoned, opts).unwrap(), "13:45");
+    let timestamp: jiff::Timestamp = "2024-03-01T13:45:00Z".parse().unwrap();
+    assert_eq!(format("hh:mm", timestamp).unwrap(), "13:45");
+
+    let value = DateValue::from_serial(45352.5, &FormatterOptions::default()).unwrap();
+    assert_eq!(
+        DateTime::try_from(value).unwrap(),
+        date(2024, 3, 1).at(12, 0, 0, 0)
+    );
+    assert!(jiff::civil::Date::try_from(DateValue::new(40_000)).is_err());
+}
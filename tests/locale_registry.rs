@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use numfmt_rs::{
+    FormatterOptions, LocaleRegistry, LocaleSettings, add_locale, format_with_options, locale_tags,
+    remove_locale,
+};
+
+fn decimal(mark: &str) -> LocaleSettings {
+    LocaleSettings {
+        decimal: Some(mark.to_string()),
+        ..LocaleSettings::default()
+    }
+}
+
+#[test]
+fn replaces_and_removes_global_locales() {
+    let opts = FormatterOptions::default().with_locale("qa_XA");
+    add_locale(decimal("!"), "qa_XA").unwrap();
+    assert_eq!(
+        format_with_options("0.0", 1.5, opts.clone()).unwrap(),
+        "1!5"
+    );
+    assert!(locale_tags().contains(&"qa_XA".to_string()));
+    assert!(locale_tags().contains(&"qa".to_string()));
+
+    add_locale(decimal("?"), "qa-XA").unwrap();
+    assert_eq!(
+        format_with_options("0.0", 1.5, opts.clone()).unwrap(),
+        "1?5"
+    );
+
+    assert!(remove_locale("qa_XA"));
+    assert!(!remove_locale("qa_XA"));
+    // the language alias still points at the first registration
+    assert_eq!(format_with_options("0.0", 1.5, opts).unwrap(), "1!5");
+    assert!(remove_locale("qa"));
+    assert!(!locale_tags().iter().any(|tag| tag.starts_with("qa")));
+}
+
+#[test]
+fn scoped_registries_are_isolated() {
+    let tenant = Arc::new(LocaleRegistry::new());
+    tenant.add(decimal("#"), "qb").unwrap();
+    assert!(tenant.contains("qb"));
+    assert!(tenant.contains("de"));
+
+    let scoped = FormatterOptions::default()
+        .with_locale("qb")
+        .with_locales(tenant.clone());
+    assert_eq!(format_with_options("0.0", 2.5, scoped).unwrap(), "2#5");
+    let global = FormatterOptions::default().with_locale("qb");
+    assert_eq!(format_with_options("0.0", 2.5, global).unwrap(), "2.5");
+
+    let pattern_locale = FormatterOptions::default().with_locales(tenant.clone());
+    assert_eq!(
+        format_with_options("[$-qb]0.0", 2.5, pattern_locale).unwrap(),
+        "2#5"
+    );
+
+    assert!(tenant.remove("qb"));
+    assert!(!tenant.tags().contains(&"qb".to_string()));
+}
@@ -0,0 +1,112 @@
+use numfmt_rs::{DateValue, FormatValue, FormatterOptions, format, format_with_options};
+
+fn fmt(pattern: &str, value: f64) -> String {
+    format(pattern, FormatValue::Number(value)).unwrap()
+}
+
+#[test]
+fn dbnum_chinese_positional() {
+    assert_eq!(fmt("[DBNum1][$-804]General", 123.0), "一百二十三");
+    assert_eq!(fmt("[DBNum1][$-804]0", 10.0), "十");
+    assert_eq!(fmt("[DBNum1][$-804]0", 1005.0), "一千〇五");
+    assert_eq!(fmt("[DBNum1][$-804]0", 100010000.0), "一亿〇一万");
+    assert_eq!(fmt("[DBNum1][$-804]#,##0", 12345.0), "一万二千三百四十五");
+    assert_eq!(fmt("[DBNum2][$-804]General", 123.0), "壹佰贰拾叁");
+    assert_eq!(fmt("[DBNum2][$-804]0.00", 10.5), "壹拾.伍零");
+    assert_eq!(fmt("[DBNum3][$-804]0", 123.0), "１百２十３");
+    assert_eq!(fmt("[DBNum2][$-404]0", 10000.0), "壹萬");
+}
+
+#[test]
+fn dbnum_japanese_and_korean() {
+    assert_eq!(fmt("[DBNum1][$-411]0", 1111.0), "千百十一");
+    assert_eq!(fmt("[DBNum2][$-411]0", 12345.0), "壱萬弐阡参百四拾五");
+    assert_eq!(fmt("[DBNum3][$-411]0", 2024.0), "２０２４");
+    assert_eq!(fmt("[DBNum4][$-412]0", 123.0), "백이십삼");
+    assert_eq!(fmt("[DBNum1][$-412]0", 15.0), "十五");
+}
+
+#[test]
+fn natnum_variants() {
+    assert_eq!(fmt("[NatNum1][$-804]0", 2024.0), "二〇二四");
+    assert_eq!(fmt("[NatNum3]0.00", 12.5), "１２.５０");
+    assert_eq!(fmt("[NatNum4][$-804]0", 110.0), "一百一十");
+    assert_eq!(fmt("[NatNum7][$-804]0", 110.0), "百十");
+    assert_eq!(fmt("[NatNum9][$-412]0", 205.0), "이영오");
+    // unsupported variants fall back to plain digits
+    assert_eq!(fmt("[NatNum9][$-804]0", 205.0), "205");
+    assert_eq!(fmt("[DBNum4][$-804]0", 205.0), "205");
+}
+
+#[test]
+fn dbnum_dates() {
+    let date = FormatValue::Date(DateValue::new(2024).with_month(12).with_day(21));
+    assert_eq!(
+        format_with_options(
+            "[DBNum1][$-804]yyyy年m月d日",
+            date,
+            FormatterOptions::default()
+        )
+        .unwrap(),
+        "二〇二四年十二月二十一日"
+    );
+}
+
+#[test]
+fn native_digits_follow_locale() {
+    let ar = FormatterOptions::default()
+        .with_locale("ar")
+        .with_native_digits(true);
+    let value = || FormatValue::Number(1234.5);
+    assert_eq!(
+        format_with_options("#,##0.00", value(), ar.clone()).unwrap(),
+        "١٬٢٣٤٫٥٠"
+    );
+    assert_eq!(
+        format_with_options(
+            "#,##0.00",
+            value(),
+            FormatterOptions::default().with_locale("ar")
+        )
+        .unwrap(),
+        "1٬234٫50"
+    );
+    assert_eq!(
+        format_with_options("General", FormatValue::Number(1e20), ar.clone()).unwrap(),
+        "١E+٢٠"
+    );
+    // the numeral byte 01 forces ASCII digits
+    assert_eq!(
+        format_with_options("[$-1000401]0", value(), ar).unwrap(),
+        "1235"
+    );
+
+    let bn = FormatterOptions::default()
+        .with_locale("bn")
+        .with_native_digits(true);
+    let date = FormatValue::Date(DateValue::new(2024).with_month(12).with_day(21));
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", date, bn).unwrap(),
+        "২০২৪-১২-২১"
+    );
+}
+
+#[test]
+fn locale_code_selects_digit_script() {
+    assert_eq!(fmt("[$-D000000]0", 123.0), "๑๒๓");
+    assert_eq!(fmt("[$-2000000]0.0", 12.5), "١٢.٥");
+    assert_eq!(fmt("[$-3000000]0", 45.0), "۴۵");
+    assert_eq!(fmt("[$-4000000]#,##0", 1234.0), "१,२३४");
+    assert_eq!(fmt("[$-2000000]yyyy", 1e10), "١٠٠٠٠٠٠٠٠٠٠");
+    assert_eq!(
+        format_with_options(
+            "0",
+            FormatValue::Number(7.0),
+            FormatterOptions::default()
+                .with_locale("fa")
+                .with_native_digits(true)
+        )
+        .unwrap(),
+        "۷"
+    );
+}
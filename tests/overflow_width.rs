@@ -0,0 +1,36 @@
+use numfmt_rs::{FormatValue, FormatterOptions, format_with_options};
+
+fn fmt<'a>(pattern: &str, value: impl Into<FormatValue<'a>>, width: usize) -> String {
+    let opts = FormatterOptions::default().with_width(Some(width));
+    format_with_options(pattern, value, opts).unwrap()
+}
+
+#[test]
+fn general_narrows_to_the_column() {
+    assert_eq!(fmt("General", 1.23456789, 11), "1.23456789");
+    assert_eq!(fmt("General", 1.23456789, 6), "1.2346");
+    assert_eq!(fmt("General", -1.23456789, 6), "-1.235");
+    assert_eq!(fmt("General", 0.0001234, 6), "0.0001");
+    assert_eq!(fmt("General", 0.000012345, 6), "1E-05");
+    assert_eq!(fmt("General", 12_345_678.0, 5), "1E+07");
+    assert_eq!(fmt("General", 1_234_567_890_123.0, 7), "1.2E+12");
+    assert_eq!(fmt("General", 99.97, 3), "100");
+    assert_eq!(fmt("General\" kg\"", 1.23456789, 8), "1.235 kg");
+    assert_eq!(fmt("[$-de]General", 1.23456789, 6), "1,2346");
+    assert_eq!(fmt("General", 1e100, 4), "######");
+}
+
+#[test]
+fn fixed_patterns_overflow() {
+    assert_eq!(fmt("0.00", 1234.5, 7), "1234.50");
+    assert_eq!(fmt("0.00", 1234.5, 6), "######");
+    assert_eq!(fmt("yyyy-mm-dd", 45000.0, 8), "######");
+    let opts = FormatterOptions {
+        overflow: "#".to_string(),
+        ..FormatterOptions::default()
+    }
+    .with_width(Some(2));
+    assert_eq!(format_with_options("0", 123.0, opts).unwrap(), "#");
+    // text spills over like in a spreadsheet
+    assert_eq!(fmt("@", "abcdef", 3), "abcdef");
+}
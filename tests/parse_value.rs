@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use numfmt_rs::{
+    FormatValue, FormatterOptions, LocaleRegistry, LocaleSettings, parse_bool, parse_date,
+    parse_number, parse_time, parse_value,
+};
+
+fn number(input: &str, options: &FormatterOptions) -> (f64, Option<String>) {
+    let parsed =
+        parse_number(input, options).unwrap_or_else(|| panic!("failed to parse number {input:?}"));
+    match parsed.value {
+        FormatValue::Number(n) => (n, parsed.format),
+        other => panic!("unexpected value {other:?}"),
+    }
+}
+
+fn serial(parsed: Option<numfmt_rs::ParsedValue>) -> (f64, Option<String>) {
+    let parsed = parsed.expect("value should parse");
+    match parsed.value {
+        FormatValue::Number(n) => (n, parsed.format),
+        other => panic!("unexpected value {other:?}"),
+    }
+}
+
+#[test]
+fn parses_plain_and_grouped_numbers() {
+    let opts = FormatterOptions::default();
+    assert_eq!(number("1234.5", &opts), (1234.5, None));
+    assert_eq!(
+        number("1,234.5", &opts),
+        (1234.5, Some("#,##0.00".to_string()))
+    );
+    assert_eq!(
+        number("-1,234", &opts),
+        (-1234.0, Some("#,##0".to_string()))
+    );
+    assert_eq!(
+        number("1.5e3", &opts),
+        (1500.0, Some("0.00E+00".to_string()))
+    );
+    assert!(parse_number("1,23", &opts).is_none());
+    assert!(parse_number("abc", &opts).is_none());
+    // out of range for f64
+    assert!(parse_number("1e400", &opts).is_none());
+    assert!(parse_number("-1e400", &opts).is_none());
+}
+
+#[test]
+fn parses_percent_currency_and_parens() {
+    let opts = FormatterOptions::default();
+    assert_eq!(number("12%", &opts), (0.12, Some("0%".to_string())));
+    assert_eq!(number("12.5 %", &opts), (0.125, Some("0.00%".to_string())));
+    assert_eq!(
+        number("$ 3.50", &opts),
+        (3.5, Some("$ #,##0.00".to_string()))
+    );
+    assert_eq!(number("-€12", &opts), (-12.0, Some("€#,##0".to_string())));
+    assert_eq!(number("12 €", &opts), (12.0, Some("#,##0 €".to_string())));
+    assert_eq!(
+        number("(100)", &opts),
+        (-100.0, Some("0_);(0)".to_string()))
+    );
+}
+
+#[test]
+fn parses_numbers_with_locale_separators() {
+    let opts = FormatterOptions::default().with_locale("de");
+    assert_eq!(
+        number("1.234,5", &opts),
+        (1234.5, Some("#,##0.00".to_string()))
+    );
+    assert!(parse_number("1.5", &opts).is_none());
+}
+
+#[test]
+fn parses_dates() {
+    let opts = FormatterOptions::default();
+    assert_eq!(
+        serial(parse_date("2024-03-01", &opts)),
+        (45352.0, Some("yyyy-mm-dd".to_string()))
+    );
+    assert_eq!(
+        serial(parse_date("3/1/2024", &opts)),
+        (45352.0, Some("m/d/yyyy".to_string()))
+    );
+    assert_eq!(
+        serial(parse_date("1 March 2024", &opts)),
+        (45352.0, Some("d mmmm yyyy".to_string()))
+    );
+    assert_eq!(
+        serial(parse_date("Mar 1, 2024", &opts)),
+        (45352.0, Some("mmm d, yyyy".to_string()))
+    );
+    assert_eq!(serial(parse_date("1900-02-29", &opts)).0, 60.0);
+    assert!(parse_date("2023-02-29", &opts).is_none());
+
+    let de = FormatterOptions::default().with_locale("de");
+    assert_eq!(
+        serial(parse_date("1.3.2024", &de)),
+        (45352.0, Some("d.m.yyyy".to_string()))
+    );
+}
+
+#[test]
+fn parses_times_and_datetimes() {
+    let opts = FormatterOptions::default();
+    assert_eq!(
+        serial(parse_time("13:45", &opts)),
+        (0.5729166666666666, Some("h:mm".to_string()))
+    );
+    assert_eq!(
+        serial(parse_time("1:45:30 PM", &opts)).1,
+        Some("h:mm:ss AM/PM".to_string())
+    );
+    assert_eq!(
+        serial(parse_time("25:30", &opts)),
+        (1.0625, Some("[h]:mm".to_string()))
+    );
+    assert_eq!(
+        serial(parse_date("2024-03-01 13:45", &opts)),
+        (45352.572916666664, Some("yyyy-mm-dd h:mm".to_string()))
+    );
+    assert!(parse_time("12:60", &opts).is_none());
+}
+
+#[test]
+fn parses_times_with_partial_ampm_markers() {
+    let registry = Arc::new(LocaleRegistry::new());
+    let settings = LocaleSettings {
+        ampm: Some(vec!["vm".to_string()]),
+        ..LocaleSettings::default()
+    };
+    registry.add(settings, "qc").unwrap();
+    let opts = FormatterOptions::default()
+        .with_locale("qc")
+        .with_locales(registry);
+    assert_eq!(serial(parse_time("1:30 vm", &opts)).0, 0.0625);
+    assert_eq!(serial(parse_time("1:30 PM", &opts)).0, 0.5625);
+}
+
+#[test]
+fn parses_booleans_and_values() {
+    let opts = FormatterOptions::default();
+    assert_eq!(
+        parse_bool("TRUE", &opts).map(|p| p.value),
+        Some(FormatValue::Boolean(true))
+    );
+    let de = FormatterOptions::default().with_locale("de");
+    assert_eq!(
+        parse_value("falsch", &de).map(|p| p.value),
+        Some(FormatValue::Boolean(false))
+    );
+    assert_eq!(
+        parse_value("12%", &opts).and_then(|p| p.format),
+        Some("0%".to_string())
+    );
+    assert!(parse_value("hello", &opts).is_none());
+}
+
+#[test]
+fn suggested_formats_reproduce_input() {
+    let opts = FormatterOptions::default();
+    for input in [
+        "1,234.50",
+        "12%",
+        "$ 3.50",
+        "12 €",
+        "2024-03-01",
+        "1 March 2024",
+        "Mar 1, 2024",
+        "1:45 PM",
+        "2024-03-01 13:45",
+    ] {
+        let parsed = parse_value(input, &opts).unwrap();
+        let pattern = parsed.format.expect("format suggestion");
+        let output = numfmt_rs::format(&pattern, parsed.value).unwrap();
+        assert_eq!(output, input, "pattern {pattern:?}");
+    }
+}
@@ -0,0 +1,32 @@
+use numfmt_rs::{
+    CacheStats, clear_pattern_cache, format, pattern_cache_stats, set_pattern_cache_capacity,
+};
+
+// The cache is process-wide, so everything runs in one test
+#[test]
+fn bounded_cache_counts_and_evicts() {
+    let before = pattern_cache_stats();
+    format("0.00", 1.0).unwrap();
+    format("0.00", 2.0).unwrap();
+    let after = pattern_cache_stats();
+    assert_eq!(after.misses - before.misses, 1);
+    assert_eq!(after.hits - before.hits, 1);
+
+    set_pattern_cache_capacity(8);
+    for i in 0..100 {
+        assert_eq!(format(&format!("0\"{i}\""), 1.0).unwrap(), format!("1{i}"));
+    }
+    let stats = pattern_cache_stats();
+    assert!(stats.entries <= 8, "{stats:?}");
+    assert!(stats.evictions - after.evictions >= 92, "{stats:?}");
+
+    clear_pattern_cache();
+    assert_eq!(pattern_cache_stats().entries, 0);
+
+    set_pattern_cache_capacity(0);
+    let CacheStats { hits, .. } = pattern_cache_stats();
+    format("0.0", 1.0).unwrap();
+    format("0.0", 1.0).unwrap();
+    let stats = pattern_cache_stats();
+    assert_eq!((stats.hits, stats.entries, stats.capacity), (hits, 0, 0));
+}
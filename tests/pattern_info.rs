@@ -0,0 +1,92 @@
+use numfmt_rs::constants::DateUnits;
+use numfmt_rs::{PatternType, get_date_info, get_info, parse_pattern};
+
+fn info(pattern: &str) -> (PatternType, String) {
+    let info = get_info(pattern).unwrap();
+    (info.kind, info.code)
+}
+
+#[test]
+fn reports_number_types_and_codes() {
+    assert_eq!(info("General"), (PatternType::General, "G".to_string()));
+    assert_eq!(info("0.00"), (PatternType::Number, "F2".to_string()));
+    assert_eq!(info("#,##0"), (PatternType::Number, ",0".to_string()));
+    assert_eq!(info("$#,##0.00"), (PatternType::Currency, "C2".to_string()));
+    assert_eq!(
+        info("[$€-407]#,##0.00"),
+        (PatternType::Currency, "C2".to_string())
+    );
+    // ISO codes in the locale token are currencies too
+    assert_eq!(
+        info("[$USD-409] #,##0.00"),
+        (PatternType::Currency, "C2".to_string())
+    );
+    assert_eq!(
+        info("[$EUR] 0.00"),
+        (PatternType::Currency, "C2".to_string())
+    );
+    assert_eq!(info("0%"), (PatternType::Percent, "P0".to_string()));
+    assert_eq!(
+        info("0.00E+00"),
+        (PatternType::Scientific, "S2".to_string())
+    );
+    assert_eq!(info("# ?/?"), (PatternType::Fraction, "G".to_string()));
+    assert_eq!(info("@"), (PatternType::Text, "@".to_string()));
+}
+
+#[test]
+fn reports_date_codes() {
+    assert_eq!(info("d-mmm-yy"), (PatternType::Date, "D1".to_string()));
+    assert_eq!(info("d-mmm"), (PatternType::Date, "D2".to_string()));
+    assert_eq!(info("mmm-yy"), (PatternType::Date, "D3".to_string()));
+    assert_eq!(
+        info("m/d/yy h:mm"),
+        (PatternType::DateTime, "D4".to_string())
+    );
+    assert_eq!(info("m/d"), (PatternType::Date, "D5".to_string()));
+    assert_eq!(info("mm/dd"), (PatternType::Date, "D5".to_string()));
+    assert_eq!(info("h:mm:ss AM/PM"), (PatternType::Time, "D6".to_string()));
+    assert_eq!(info("h:mm AM/PM"), (PatternType::Time, "D7".to_string()));
+    assert_eq!(info("h:mm:ss"), (PatternType::Time, "D8".to_string()));
+    assert_eq!(info("h:mm"), (PatternType::Time, "D9".to_string()));
+}
+
+#[test]
+fn reports_color_parentheses_and_flags() {
+    let info = get_info("#,##0.00_);[Red](#,##0.00)").unwrap();
+    assert!(info.color);
+    assert!(info.grouped);
+    assert!(!info.parentheses);
+    assert_eq!(info.decimals, 2);
+    assert_eq!(info.code, ",2-");
+
+    assert_eq!(get_info("(0)").unwrap().code, "F0()");
+
+    let pattern = parse_pattern("0.0%").unwrap();
+    assert!(pattern.is_percent());
+    assert!(!pattern.is_date());
+    assert!(!pattern.is_text());
+    assert!(parse_pattern("yyyy").unwrap().is_date());
+    assert!(parse_pattern("@").unwrap().is_text());
+    assert!(!parse_pattern("0;0;0;@").unwrap().is_text());
+}
+
+#[test]
+fn reports_date_parts() {
+    let info = get_date_info("yyyy-mm-dd h:mm AM/PM").unwrap();
+    assert!(info.year && info.month && info.day && info.hours && info.minutes);
+    assert!(!info.seconds);
+    assert_eq!(info.clock, 12);
+    assert!(info.elapsed.is_empty());
+
+    let info = get_date_info("[h]:mm:ss.00").unwrap();
+    assert!(!info.year && !info.month && !info.day);
+    assert!(info.hours && info.minutes && info.seconds);
+    assert_eq!(info.clock, 24);
+    assert_eq!(info.elapsed, DateUnits::HOUR);
+
+    let info = get_date_info("[mm]:ss;0").unwrap();
+    assert_eq!(info.elapsed, DateUnits::MINUTE);
+
+    assert_eq!(get_date_info("0.00").unwrap().elapsed, DateUnits::empty());
+}
@@ -0,0 +1,77 @@
+use numfmt_rs::{FormatterOptions, RoundingMode, format_with_options};
+
+fn fmt(pattern: &str, value: f64, mode: RoundingMode) -> String {
+    let opts = FormatterOptions::default().with_rounding(mode);
+    format_with_options(pattern, value, opts).unwrap()
+}
+
+#[test]
+fn rounds_fraction_and_integer_digits() {
+    use RoundingMode::*;
+    let cases = [
+        ("0.00", 2.345, ["2.35", "2.34", "2.34", "2.34", "2.35"]),
+        (
+            "0.00",
+            -2.345,
+            ["-2.35", "-2.34", "-2.34", "-2.35", "-2.34"],
+        ),
+        ("0", 2.5, ["3", "2", "2", "2", "3"]),
+        ("0", 3.5, ["4", "4", "3", "3", "4"]),
+        (
+            "#,##0",
+            1234.1,
+            ["1,234", "1,234", "1,234", "1,234", "1,235"],
+        ),
+        ("0.0", 0.29, ["0.3", "0.3", "0.2", "0.2", "0.3"]),
+    ];
+    for (pattern, value, expected) in cases {
+        for (mode, want) in [HalfAwayFromZero, HalfEven, Truncate, Floor, Ceiling]
+            .into_iter()
+            .zip(expected)
+        {
+            assert_eq!(
+                fmt(pattern, value, mode),
+                want,
+                "{pattern} {value} {mode:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn truncation_is_free_of_binary_noise() {
+    assert_eq!(fmt("0.00", 1.1 * 3.0, RoundingMode::Truncate), "3.30");
+    assert_eq!(fmt("0%", 0.29, RoundingMode::Truncate), "29%");
+    assert_eq!(fmt("0.00", 0.125, RoundingMode::HalfEven), "0.12");
+}
+
+#[test]
+fn rounds_mantissas_and_numerators() {
+    assert_eq!(
+        fmt("0.0E+00", 99_950.0, RoundingMode::HalfAwayFromZero),
+        "1.0E+05"
+    );
+    assert_eq!(fmt("0.0E+00", 99_950.0, RoundingMode::Truncate), "9.9E+04");
+    assert_eq!(fmt("0.0E+00", 12_250.0, RoundingMode::HalfEven), "1.2E+04");
+    assert_eq!(fmt("# ?/4", 2.3, RoundingMode::Truncate), "2 1/4");
+    assert_eq!(fmt("# ?/4", 2.3, RoundingMode::Ceiling), "2 2/4");
+    assert_eq!(fmt("# ?/2", -2.25, RoundingMode::Floor), "-2 1/2");
+}
+
+#[test]
+fn rounds_sub_seconds() {
+    // 12:00:00.75
+    let serial = 0.5 + 0.75 / 86_400.0;
+    assert_eq!(
+        fmt("hh:mm:ss", serial, RoundingMode::HalfAwayFromZero),
+        "12:00:01"
+    );
+    assert_eq!(fmt("hh:mm:ss", serial, RoundingMode::Truncate), "12:00:00");
+    assert_eq!(fmt("hh:mm:ss.0", serial, RoundingMode::Floor), "12:00:00.7");
+    assert_eq!(
+        fmt("hh:mm:ss.0", serial, RoundingMode::Ceiling),
+        "12:00:00.8"
+    );
+    let tie = 0.5 + 0.5 / 86_400.0;
+    assert_eq!(fmt("hh:mm:ss", tie, RoundingMode::HalfEven), "12:00:00");
+}
@@ -0,0 +1,140 @@
+use numfmt_rs::{
+    DateValue, FormatterOptions, SerialError, format_with_options,
+    serial::{date_to_serial, hijri_to_serial, serial_to_date, serial_to_hijri},
+};
+
+fn ymd(year: i32, month: u8, day: u8) -> DateValue {
+    DateValue::new(year).with_month(month).with_day(day)
+}
+
+fn at_midnight(date: DateValue) -> DateValue {
+    date.with_time(0, 0, 0).with_millisecond(0)
+}
+
+#[test]
+fn round_trips_gregorian_serials() {
+    let opts = FormatterOptions::default();
+    let date = ymd(2024, 3, 1).with_time(18, 30, 15).with_millisecond(250);
+    let serial = date_to_serial(&date, &opts).unwrap();
+    assert_eq!(serial_to_date(serial, &opts).unwrap(), date);
+
+    for serial in [
+        -693_000.0,
+        -1.0,
+        0.0,
+        1.0,
+        59.0,
+        60.0,
+        61.0,
+        45_352.0,
+        2_958_465.0,
+    ] {
+        let date = serial_to_date(serial, &opts).unwrap();
+        assert_eq!(date_to_serial(&date, &opts).unwrap(), serial, "{date:?}");
+    }
+}
+
+#[test]
+fn honors_leap_1900_and_1904() {
+    let opts = FormatterOptions::default();
+    assert_eq!(date_to_serial(&ymd(1900, 2, 29), &opts), Ok(60.0));
+    assert_eq!(
+        date_to_serial(&ymd(1900, 2, 28).with_time(12, 0, 0), &opts),
+        Ok(59.5)
+    );
+    assert_eq!(date_to_serial(&ymd(1899, 12, 29), &opts), Ok(-1.0));
+    assert!(date_to_serial(&ymd(1899, 12, 30), &opts).is_err());
+    assert_eq!(
+        serial_to_date(60.0, &opts),
+        Ok(at_midnight(ymd(1900, 2, 29)))
+    );
+
+    let no_leap = FormatterOptions {
+        leap_1900: false,
+        ..Default::default()
+    };
+    assert_eq!(date_to_serial(&ymd(1900, 1, 1), &no_leap), Ok(2.0));
+    assert!(matches!(
+        date_to_serial(&ymd(1900, 2, 29), &no_leap),
+        Err(SerialError::InvalidDate(_))
+    ));
+
+    let mac = FormatterOptions::default().with_date_1904(true);
+    assert_eq!(date_to_serial(&ymd(1904, 1, 1), &mac), Ok(0.0));
+    assert_eq!(date_to_serial(&ymd(1900, 1, 1), &mac), Ok(-1460.0));
+    assert_eq!(
+        serial_to_date(43_890.0, &mac),
+        Ok(at_midnight(ymd(2024, 3, 1)))
+    );
+}
+
+#[test]
+fn formats_pre_1900_date_values() {
+    let fmt = |date: DateValue, opts: &FormatterOptions| {
+        format_with_options("yyyy-mm-dd", date, opts.clone()).unwrap()
+    };
+    let opts = FormatterOptions::default();
+    assert_eq!(fmt(ymd(1850, 6, 15), &opts), "1850-06-15");
+    assert_eq!(fmt(ymd(1899, 12, 29), &opts), "1899-12-29");
+    assert_eq!(fmt(ymd(1900, 1, 1), &opts), "1900-01-01");
+    assert_eq!(fmt(ymd(1900, 3, 1), &opts), "1900-03-01");
+
+    let no_leap = FormatterOptions {
+        leap_1900: false,
+        ..Default::default()
+    };
+    assert_eq!(fmt(ymd(1850, 6, 15), &no_leap), "1850-06-15");
+    assert_eq!(fmt(ymd(1899, 12, 31), &no_leap), "1899-12-31");
+    assert_eq!(fmt(ymd(1900, 1, 1), &no_leap), "1900-01-01");
+}
+
+#[test]
+fn round_trips_hijri_serials() {
+    let opts = FormatterOptions::default();
+    assert_eq!(
+        serial_to_hijri(45_352.0, &opts),
+        Ok(at_midnight(ymd(1445, 8, 21)))
+    );
+    assert_eq!(hijri_to_serial(&ymd(1445, 8, 21), &opts), Ok(45_352.0));
+    assert_eq!(
+        serial_to_hijri(60.0, &opts),
+        Err(SerialError::NoSuchDate(60.0))
+    );
+
+    for serial in (-600_000..2_900_000).step_by(997).map(f64::from) {
+        let date = serial_to_hijri(serial, &opts).unwrap();
+        assert_eq!(hijri_to_serial(&date, &opts), Ok(serial), "{date:?}");
+    }
+    assert!(hijri_to_serial(&ymd(1445, 13, 1), &opts).is_err());
+}
+
+#[test]
+fn rejects_invalid_dates_and_out_of_range_serials() {
+    let opts = FormatterOptions::default();
+    assert!(matches!(
+        date_to_serial(&ymd(2023, 2, 29), &opts),
+        Err(SerialError::InvalidDate(_))
+    ));
+    assert!(matches!(
+        date_to_serial(&ymd(2024, 1, 1).with_time(24, 0, 0), &opts),
+        Err(SerialError::InvalidDate(_))
+    ));
+    assert_eq!(
+        serial_to_date(
+            -1.0,
+            &FormatterOptions {
+                date_span_large: false,
+                ..Default::default()
+            }
+        ),
+        Err(SerialError::OutOfRange(-1.0))
+    );
+    assert!(matches!(
+        date_to_serial(&ymd(100_000, 1, 1), &opts),
+        Err(SerialError::OutOfRange(_))
+    ));
+    assert_eq!(
+        serial_to_date(f64::NAN, &opts).map_err(|e| e.to_string()),
+        Err("serial NaN is outside the supported date range".to_string())
+    );
+}
@@ -0,0 +1,77 @@
+use numfmt_rs::{DateValue, FormatterError, FormatterOptions, TimeZone, format_with_options};
+
+const PATTERN: &str = "yyyy-mm-dd hh:mm";
+
+fn fmt(date: DateValue, opts: FormatterOptions) -> String {
+    format_with_options(PATTERN, date, opts).unwrap()
+}
+
+fn utc_instant() -> DateValue {
+    DateValue::new(2024)
+        .with_month(3)
+        .with_day(1)
+        .with_time(23, 30, 0)
+        .with_zone(TimeZone::UTC)
+}
+
+#[test]
+fn converts_zoned_values_into_target_zone() {
+    let opts = FormatterOptions::default().with_timezone(TimeZone::Offset(5 * 3600 + 1800));
+    assert_eq!(fmt(utc_instant(), opts), "2024-03-02 05:00");
+
+    let opts = FormatterOptions::default().with_timezone(TimeZone::named("-08:00"));
+    assert_eq!(fmt(utc_instant(), opts), "2024-03-01 15:30");
+
+    let tokyo = DateValue::new(2024)
+        .with_month(3)
+        .with_day(1)
+        .with_time(8, 0, 0)
+        .with_offset(9 * 3600);
+    assert_eq!(fmt(tokyo, FormatterOptions::default()), "2024-02-29 23:00");
+}
+
+#[test]
+fn floating_and_ignored_zones_keep_wall_clock() {
+    let opts = FormatterOptions::default().with_timezone(TimeZone::Offset(3600));
+    let floating = DateValue::new(2024)
+        .with_month(3)
+        .with_day(1)
+        .with_time(23, 30, 0);
+    assert_eq!(fmt(floating, opts.clone()), "2024-03-01 23:30");
+    assert_eq!(
+        fmt(utc_instant(), opts.with_ignore_timezone(true)),
+        "2024-03-01 23:30"
+    );
+}
+
+#[cfg(not(feature = "tz"))]
+#[test]
+fn named_zones_need_tz_feature() {
+    let opts = FormatterOptions::default().with_timezone(TimeZone::named("Europe/Berlin"));
+    let result = format_with_options(PATTERN, utc_instant(), opts);
+    assert!(matches!(result, Err(FormatterError::InvalidTimeZone(_))));
+}
+
+#[cfg(feature = "tz")]
+#[test]
+fn resolves_iana_zones() {
+    let opts = FormatterOptions::default().with_timezone(TimeZone::named("Europe/Berlin"));
+    assert_eq!(fmt(utc_instant(), opts.clone()), "2024-03-02 00:30");
+    let summer = DateValue::new(2024)
+        .with_month(7)
+        .with_day(1)
+        .with_time(12, 0, 0)
+        .with_zone(TimeZone::UTC);
+    assert_eq!(fmt(summer, opts), "2024-07-01 14:00");
+
+    let nyc = DateValue::new(2024)
+        .with_month(7)
+        .with_day(1)
+        .with_time(12, 0, 0)
+        .with_zone(TimeZone::named("America/New_York"));
+    assert_eq!(fmt(nyc, FormatterOptions::default()), "2024-07-01 16:00");
+
+    let opts = FormatterOptions::default().with_timezone(TimeZone::named("Mars/Olympus"));
+    let result = format_with_options(PATTERN, utc_instant(), opts);
+    assert!(matches!(result, Err(FormatterError::InvalidTimeZone(_))));
+}